@@ -0,0 +1,63 @@
+use crate::{html, read_cache, write_cache, Input, InputError};
+use std::path::PathBuf;
+
+impl Input {
+    /// Returns the accepted answer recorded for `part` of `day` in `year`, if any. Days solved before answers were
+    /// recorded fall back to the answer shown on the cached puzzle page.
+    pub fn answer(&self, year: u16, day: u8, part: u8) -> Result<Option<String>, InputError> {
+        if let Some(answer) = read_cache(&self.answer_path(year, day, part))? {
+            return Ok(Some(answer));
+        }
+        let page = read_cache(&self.puzzle_path(year, day))?;
+        Ok(page.and_then(|page| html::answers(&page).into_iter().nth(usize::from(part) - 1)))
+    }
+
+    pub fn record_answer(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<(), InputError> {
+        write_cache(&self.answer_path(year, day, part), answer)
+    }
+
+    pub(crate) fn answer_path(&self, year: u16, day: u8, part: u8) -> PathBuf {
+        self.day_path(year, day).join(format!("answer{}", part))
+    }
+
+    /// Fetches the puzzle page of a day that was already solved and records the answer it shows for `part`.
+    pub fn record_solved_answer(&self, year: u16, day: u8, part: u8) -> Result<Option<String>, InputError> {
+        let page = self.fetch_puzzle_page(year, day)?;
+        let answer = html::answers(&page).into_iter().nth(usize::from(part) - 1);
+        if let Some(answer) = &answer {
+            self.record_answer(year, day, part, answer)?;
+        }
+        Ok(answer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock;
+    use url::Url;
+
+    #[test]
+    fn test_answers() {
+        let cache_path = mock::TempDir::new("answer");
+        let input = mock::input(&cache_path, Url::parse("http://localhost/").unwrap());
+        assert_eq!(input.answer(2022, 5, 1).unwrap(), None);
+        input.record_answer(2022, 5, 1, "CMZ").unwrap();
+        assert_eq!(input.answer(2022, 5, 1).unwrap().as_deref(), Some("CMZ"));
+        assert_eq!(input.answer(2022, 5, 2).unwrap(), None);
+        assert_eq!(input.answer(2021, 5, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_solved_answers() {
+        let cache_path = mock::TempDir::new("solved");
+        let page = "<main><article><p>One</p></article><p>Your puzzle answer was <code>24000</code>.</p>\
+                    <article><p>Two</p></article><p>Your puzzle answer was <code>45000</code>.</p></main>";
+        let (url, server) = mock::serve(&[(200, page)]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.record_solved_answer(2022, 1, 2).unwrap().as_deref(), Some("45000"));
+        assert_eq!(input.answer(2022, 1, 2).unwrap().as_deref(), Some("45000"));
+        // Part one wasn't recorded but is read off the cached page
+        assert_eq!(input.answer(2022, 1, 1).unwrap().as_deref(), Some("24000"));
+        assert_eq!(server.join().unwrap()[0].path, "/2022/day/1");
+    }
+}
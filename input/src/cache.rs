@@ -0,0 +1,178 @@
+use crate::InputError;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Reads a cached file, `None` when it has not been cached yet.
+pub(crate) fn read_cache(path: &Path) -> Result<Option<String>, InputError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(InputError::ReadCache {
+            source: e,
+            path: path.to_string_lossy().to_string(),
+        }),
+    }
+}
+
+/// Writes to a temporary file beside `path` and renames it into place, so an interrupted run never
+/// leaves a truncated file behind.
+pub(crate) fn write_cache(path: &Path, contents: &str) -> Result<(), InputError> {
+    let caching = |e, path: &Path| InputError::Caching {
+        source: e,
+        path: path.to_string_lossy().to_string(),
+    };
+    if let Some(dir_path) = path.parent() {
+        fs::create_dir_all(dir_path).map_err(|e| caching(e, dir_path))?;
+    }
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, contents).map_err(|e| caching(e, &tmp_path))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        caching(e, path)
+    })
+}
+
+pub(crate) fn sha256(contents: &str) -> String {
+    Sha256::digest(contents.as_bytes()).iter().fold(String::with_capacity(64), |mut rtn, b| {
+        let _ = write!(rtn, "{:02x}", b);
+        rtn
+    })
+}
+
+/// Where and when a cached input came from, stored as `<file>.meta` beside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// Seconds since the unix epoch
+    pub fetched: u64,
+    pub length: usize,
+    pub sha256: String,
+    pub source: String,
+}
+
+impl Metadata {
+    pub fn new(contents: &str, source: &str) -> Self {
+        Self {
+            fetched: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            length: contents.len(),
+            sha256: sha256(contents),
+            source: source.to_string(),
+        }
+    }
+
+    pub fn path(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".meta");
+        path.with_file_name(name)
+    }
+
+    pub fn matches(&self, contents: &str) -> bool {
+        self.length == contents.len() && self.sha256 == sha256(contents)
+    }
+
+    /// Metadata for the file at `path`, `None` for files cached before metadata was recorded.
+    pub fn read(path: &Path) -> Result<Option<Self>, InputError> {
+        let meta_path = Self::path(path);
+        let Some(contents) = read_cache(&meta_path)? else { return Ok(None) };
+        let invalid = || InputError::InvalidMetadata {
+            path: meta_path.to_string_lossy().to_string(),
+        };
+        let field = |name: &str| {
+            contents
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key.trim() == name)
+                .map(|(_, value)| value.trim().to_string())
+                .ok_or_else(invalid)
+        };
+        Ok(Some(Self {
+            fetched: field("fetched")?.parse().map_err(|_| invalid())?,
+            length: field("length")?.parse().map_err(|_| invalid())?,
+            sha256: field("sha256")?,
+            source: field("source")?,
+        }))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), InputError> {
+        let contents = format!(
+            "fetched = {}\nlength = {}\nsha256 = {}\nsource = {}\n",
+            self.fetched, self.length, self.sha256, self.source
+        );
+        write_cache(&Self::path(path), &contents)
+    }
+}
+
+/// Reads a file cached with `write_verified`, failing when it no longer matches its metadata.
+pub(crate) fn read_verified(path: &Path) -> Result<Option<String>, InputError> {
+    let Some(contents) = read_cache(path)? else { return Ok(None) };
+    match Metadata::read(path)? {
+        Some(meta) if !meta.matches(&contents) => Err(InputError::CorruptCache {
+            path: path.to_string_lossy().to_string(),
+        }),
+        _ => Ok(Some(contents)),
+    }
+}
+
+/// Caches `contents` along with metadata recording where it came from.
+pub(crate) fn write_verified(path: &Path, contents: &str, source: &str) -> Result<(), InputError> {
+    write_cache(path, contents)?;
+    Metadata::new(contents, source).write(path)
+}
+
+/// Why a downloaded input can't be a puzzle input, such as a login page or an error message served in its place.
+pub(crate) fn unexpected_body(body: &str) -> Option<&'static str> {
+    let start = body.trim_start();
+    if start.is_empty() {
+        Some("empty response")
+    } else if start.get(..200).unwrap_or(start).to_ascii_lowercase().contains("<html") || start.starts_with("<!") {
+        Some("response is an html page")
+    } else if start.starts_with("Puzzle inputs differ by user") {
+        Some("not logged in, the session is missing or expired")
+    } else if start.starts_with("Please don't repeatedly request") {
+        Some("puzzle is not unlocked yet")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn test_verified_cache() {
+        let dir = mock::TempDir::new("cache");
+        let path = dir.join("input");
+        write_verified(&path, "1\n2\n", "http://localhost/2022/day/1/input").unwrap();
+        assert_eq!(read_verified(&path).unwrap().as_deref(), Some("1\n2\n"));
+        let meta = Metadata::read(&path).unwrap().unwrap();
+        assert_eq!(meta.length, 4);
+        assert_eq!(meta.source, "http://localhost/2022/day/1/input");
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        // A truncated write is detected instead of trusted
+        fs::write(&path, "1\n").unwrap();
+        assert!(matches!(read_verified(&path), Err(InputError::CorruptCache { .. })));
+
+        // Files cached before metadata existed are still read
+        fs::remove_file(Metadata::path(&path)).unwrap();
+        assert_eq!(read_verified(&path).unwrap().as_deref(), Some("1\n"));
+    }
+
+    #[test]
+    fn test_unexpected_body() {
+        assert_eq!(unexpected_body("1\n2\n"), None);
+        assert!(unexpected_body("").is_some());
+        assert!(unexpected_body("<!DOCTYPE html>\n<html>").is_some());
+        // Some inputs really do start with an angle bracket
+        assert_eq!(unexpected_body("<<>><>\n"), None);
+        assert!(unexpected_body("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n").is_some());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::schedule::{is_unlocked, unlocks_at};
+use crate::{read_cache, write_cache, InputError, Settings};
+use attohttpc::body::Body;
+use attohttpc::{RequestBuilder, Response};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+pub(crate) const LAST_REQUEST: &str = "last_request";
+pub(crate) const REQUEST_LOG: &str = "requests.log";
+
+pub fn default_user_agent() -> String {
+    format!(
+        "github.com/j-t-d/aoc2022 input/{} by {}",
+        env!("CARGO_PKG_VERSION"),
+        env!("CARGO_PKG_AUTHORS").replace(':', ", ")
+    )
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+/// Http access to the puzzle site, shared by everything that talks to the network.
+pub struct Client {
+    pub(crate) url: Url,
+    pub(crate) session: String,
+    pub(crate) offline: bool,
+    pub(crate) user_agent: String,
+    pub(crate) min_interval: Duration,
+    /// Directory holding the throttle timestamp and request log
+    pub(crate) state_path: PathBuf,
+    pub(crate) request_lock: Mutex<()>,
+}
+
+impl Client {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            url: settings.url.value.clone(),
+            session: settings.session.value.clone(),
+            offline: settings.offline.value,
+            user_agent: settings.user_agent.value.clone(),
+            min_interval: settings.min_interval.value,
+            state_path: settings.cache_path.value.clone(),
+            request_lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn day_url(&self, year: u16, day: u8, page: Option<&str>) -> Url {
+        let day = day.to_string();
+        let mut path = vec!["day", day.as_str()];
+        path.extend(page);
+        self.year_url(year, &path)
+    }
+
+    /// `path` below the event for `year`.
+    pub(crate) fn year_url(&self, year: u16, path: &[&str]) -> Url {
+        let mut new_url = self.url.clone();
+        new_url
+            .path_segments_mut()
+            .expect("Is base URL")
+            .pop_if_empty()
+            .push(&year.to_string())
+            .extend(path);
+        new_url
+    }
+
+    /// Fetches a page for `day`, failing without a request when the puzzle has not unlocked yet.
+    pub(crate) fn fetch_day(&self, year: u16, day: u8, page: Option<&str>) -> Result<String, InputError> {
+        if self.offline {
+            return Err(InputError::Offline);
+        }
+        if !is_unlocked(year, day) {
+            return Err(InputError::NotYetUnlocked {
+                unlocks_at: unlocks_at(year, day),
+            });
+        }
+        self.fetch(self.day_url(year, day, page))
+    }
+
+    pub(crate) fn fetch(&self, url: Url) -> Result<String, InputError> {
+        if self.offline {
+            return Err(InputError::Offline);
+        }
+        let response = self.send(attohttpc::get(url.as_str()), InputError::HttpGet)?;
+        let status = response.status();
+        if response.is_success() {
+            response.text().map_err(InputError::HttpGet)
+        } else if status == attohttpc::StatusCode::BAD_REQUEST && response.text().is_ok_and(|body| body.contains("Please log in")) {
+            Err(InputError::SessionExpired)
+        } else {
+            Err(InputError::GetFailed { status: status.to_string() })
+        }
+    }
+
+    /// Sends `request` with the session cookie and User-Agent, waiting until at least the minimum interval has
+    /// passed since the last request made by any run, and appends the outcome to the request log.
+    pub(crate) fn send<B: Body>(&self, request: RequestBuilder<B>, map_err: fn(attohttpc::Error) -> InputError) -> Result<Response, InputError> {
+        let _guard = self.request_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let last_path = self.state_path.join(LAST_REQUEST);
+        if let Some(last) = read_cache(&last_path)?.and_then(|last| last.trim().parse::<u64>().ok()) {
+            let next = Duration::from_millis(last) + self.min_interval;
+            if let Some(wait) = next.checked_sub(since_epoch()) {
+                thread::sleep(wait);
+            }
+        }
+
+        let mut request = request
+            .header_append(attohttpc::header::COOKIE, format!("session={}", &self.session))
+            .header(attohttpc::header::USER_AGENT, &self.user_agent);
+        let inspect = request.inspect();
+        let line = format!("{} {}", inspect.method(), inspect.url());
+        let response = request.send();
+        let sent = since_epoch();
+        write_cache(&last_path, &sent.as_millis().to_string())?;
+
+        let outcome = match &response {
+            Ok(response) => response.status().to_string(),
+            Err(e) => format!("error {}", e),
+        };
+        let log_path = self.state_path.join(REQUEST_LOG);
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .and_then(|mut log| writeln!(log, "{} {} {}", sent.as_secs(), line, outcome))
+            .map_err(|e| InputError::Caching {
+                source: e,
+                path: log_path.to_string_lossy().to_string(),
+            })?;
+
+        response.map_err(map_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock, InputError};
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_throttle_and_log() {
+        let cache_path = mock::TempDir::new("client");
+        let (url, server) = mock::serve(&[(200, "1\n"), (404, "missing")]);
+        let mut client = mock::client(&cache_path, url);
+        client.min_interval = Duration::from_millis(200);
+        let input = mock::input_with(&cache_path, client);
+
+        let now = Instant::now();
+        assert_eq!(input.get(2022, 1).unwrap(), "1\n");
+        assert!(matches!(input.get(2022, 2), Err(InputError::GetFailed { .. })));
+        assert!(now.elapsed() >= Duration::from_millis(200));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].header("user-agent").unwrap().starts_with("github.com/j-t-d/aoc2022"));
+        let log = fs::read_to_string(cache_path.join(super::REQUEST_LOG)).unwrap();
+        let lines = log.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("/2022/day/1/input 200 OK"), "{}", lines[0]);
+        assert!(lines[1].ends_with("/2022/day/2/input 404 Not Found"), "{}", lines[1]);
+        assert!(!log.contains("token"));
+    }
+}
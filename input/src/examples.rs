@@ -0,0 +1,89 @@
+use crate::{html, read_cache, write_cache, Input, InputError};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Records how many examples were found, so a page without any isn't searched again.
+const COUNT: &str = "count";
+
+impl Input {
+    /// Example inputs from the puzzle page for `day`, cached beside the real input and extracted again whenever
+    /// the page is refreshed, since part two can bring new examples.
+    pub fn examples(&self, year: u16, day: u8) -> Result<Vec<String>, InputError> {
+        let examples_path = self.day_path(year, day).join("examples");
+        let (page, fetched) = self.puzzle_page(year, day)?;
+        if !fetched {
+            if let Some(cached) = read_examples(&examples_path)? {
+                return Ok(cached);
+            }
+        }
+
+        let examples = html::code_blocks(&page);
+        // Replace whatever was extracted from an older copy of the page
+        match fs::remove_dir_all(&examples_path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                return Err(InputError::Caching {
+                    source: e,
+                    path: examples_path.display().to_string(),
+                })
+            }
+            _ => {}
+        }
+        for (index, example) in examples.iter().enumerate() {
+            write_cache(&examples_path.join((index + 1).to_string()), example)?;
+        }
+        write_cache(&examples_path.join(COUNT), &examples.len().to_string())?;
+        Ok(examples)
+    }
+}
+
+/// The cached examples, `None` if they were never extracted or the cache predates the count.
+fn read_examples(examples_path: &Path) -> Result<Option<Vec<String>>, InputError> {
+    let Some(count) = read_cache(&examples_path.join(COUNT))?.and_then(|count| count.trim().parse::<usize>().ok()) else {
+        return Ok(None);
+    };
+    let mut cached = Vec::with_capacity(count);
+    for index in 1..=count {
+        match read_cache(&examples_path.join(index.to_string()))? {
+            Some(example) => cached.push(example),
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(cached))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock;
+
+    #[test]
+    fn test_examples() {
+        let cache_path = mock::TempDir::new("examples");
+        let part1 = "<main><article><p>For example:</p><pre><code>A Y\nB X\nC Z\n</code></pre><p>Then <code>15</code>.</p></article></main>";
+        let both = "<main><article><p>For example:</p><pre><code>A Y\nB X\nC Z\n</code></pre></article>\
+                    <article><p>Part two:</p><pre><code>1 &lt; 2\n</code></pre></article></main>";
+        let empty = "<main><article><p>One</p></article><article><p>Two</p></article></main>";
+        let (url, server) = mock::serve(&[(200, part1), (200, both), (200, empty)]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.examples(2022, 2).unwrap(), vec!["A Y\nB X\nC Z\n".to_string()]);
+        // Unsolved days are served from the cache
+        assert_eq!(input.examples(2022, 2).unwrap(), vec!["A Y\nB X\nC Z\n".to_string()]);
+        // Once part one is solved the page is fetched again and part two's example picked up
+        input.record_answer(2022, 2, 1, "15").unwrap();
+        let expected = vec!["A Y\nB X\nC Z\n".to_string(), "1 < 2\n".to_string()];
+        assert_eq!(input.examples(2022, 2).unwrap(), expected);
+        assert_eq!(input.examples(2022, 2).unwrap(), expected);
+        // The description comes from the same cached page
+        assert!(input.puzzle(2022, 2).unwrap().contains("Part two:"));
+
+        // A page without examples is remembered too
+        assert!(input.examples(2022, 3).unwrap().is_empty());
+        assert!(input.examples(2022, 3).unwrap().is_empty());
+
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests.iter().map(|request| request.path.as_str()).collect::<Vec<_>>(),
+            vec!["/2022/day/2", "/2022/day/2", "/2022/day/3"]
+        );
+    }
+}
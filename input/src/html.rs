@@ -0,0 +1,191 @@
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut rtn = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        rtn.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let decoded = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((decoded, end))
+        });
+        match decoded {
+            Some((decoded, end)) => {
+                rtn.push(decoded);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                rtn.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    rtn.push_str(rest);
+    rtn
+}
+
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut rtn = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => rtn.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&rtn)
+}
+
+/// Text content of every `<pre><code>` block in `html`, in document order.
+pub(crate) fn code_blocks(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let end = rest.find("</code></pre>").unwrap_or(rest.len());
+        blocks.push(strip_tags(&rest[..end]));
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// Accepted answers the puzzle page shows once a part is solved, in part order.
+pub(crate) fn answers(html: &str) -> Vec<String> {
+    const PREFIX: &str = "Your puzzle answer was <code>";
+    html.match_indices(PREFIX)
+        .filter_map(|(start, _)| {
+            let rest = &html[start + PREFIX.len()..];
+            rest.find("</code>").map(|end| decode_entities(&rest[..end]))
+        })
+        .collect()
+}
+
+/// Inner html of every `<article>` element in `html`.
+pub(crate) fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        rest = &rest[start..];
+        let Some(open_end) = rest.find('>') else { break };
+        rest = &rest[open_end + 1..];
+        let end = rest.find("</article>").unwrap_or(rest.len());
+        articles.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    articles
+}
+
+/// Renders the small subset of html used in puzzle descriptions as markdown.
+pub(crate) fn to_markdown(html: &str) -> String {
+    let mut rtn = String::new();
+    let mut pre = false;
+    let mut code = false;
+    let mut rest = html;
+    loop {
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = decode_entities(&rest[..text_end]);
+        if pre {
+            rtn.push_str(&text);
+        } else if rtn.is_empty() || rtn.ends_with('\n') {
+            rtn.push_str(text.replace('\n', " ").trim_start());
+        } else {
+            rtn.push_str(&text.replace('\n', " "));
+        }
+        rest = &rest[text_end..];
+        let Some(tag_end) = rest.find('>') else { break };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        match (name.to_ascii_lowercase().as_str(), closing) {
+            ("h2", false) => rtn.push_str("## "),
+            ("h2", true) | ("p", true) | ("ul", true) => rtn.push_str("\n\n"),
+            ("pre", false) => {
+                pre = true;
+                rtn.push_str("```\n");
+            }
+            ("pre", true) => {
+                pre = false;
+                if !rtn.ends_with('\n') {
+                    rtn.push('\n');
+                }
+                rtn.push_str("```\n\n");
+            }
+            ("code", _) if !pre => {
+                code = !closing;
+                rtn.push('`');
+            }
+            ("em", _) if !pre && !code => rtn.push('*'),
+            ("li", false) => rtn.push_str("- "),
+            ("li", true) | ("br", _) => rtn.push('\n'),
+            _ => {}
+        }
+    }
+
+    let mut markdown = String::new();
+    for line in rtn.lines().map(str::trim_end) {
+        if line.is_empty() && (markdown.is_empty() || markdown.ends_with("\n\n")) {
+            continue;
+        }
+        markdown.push_str(line);
+        markdown.push('\n');
+    }
+    markdown.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &amp; &#39;c&#x27; &unknown; & d"), "a <b> & 'c' &unknown; & d");
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let html = "<p>For example:</p>\n<pre><code>    [D]    \n[N] <em>[C]</em>\n&gt; 1\n</code></pre>\n<p>and</p><pre><code>A Y\n</code></pre>";
+        assert_eq!(code_blocks(html), vec!["    [D]    \n[N] [C]\n> 1\n", "A Y\n"]);
+        assert!(code_blocks("<p>nothing</p>").is_empty());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let page = r#"<main><article class="day-desc"><h2>--- Day 6: Tuning Trouble ---</h2><p>The preparations are
+finally complete; <em>start-of-packet</em> marker &amp; such.</p>
+<ul>
+<li><code>bvwbjplbgvbhsrlpgdmjqwftvncz</code>: first marker after character <code><em>5</em></code></li>
+</ul>
+<pre><code>mjqjpq<em>mgbl</em>
+</code></pre>
+</article><p>Your puzzle answer was <code>1210</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <a href="/2022/day/6">again</a>.</p></article></main>"#;
+        let markdown = articles(page).into_iter().map(to_markdown).collect::<Vec<_>>();
+        assert_eq!(markdown.len(), 2);
+        assert_eq!(
+            markdown[0],
+            "## --- Day 6: Tuning Trouble ---\n\nThe preparations are finally complete; *start-of-packet* marker & such.\n\n- `bvwbjplbgvbhsrlpgdmjqwftvncz`: first marker after character `5`\n\n```\nmjqjpqmgbl\n```"
+        );
+        assert_eq!(markdown[1], "## --- Part Two ---\n\nNow again.");
+        assert_eq!(answers(page), vec!["1210"]);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::cache::{read_cache, unexpected_body, write_verified};
+use crate::{Input, InputError, Metadata};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The puzzle site asks for private leaderboards to be fetched no more than once every 15 minutes.
+pub const LEADERBOARD_REFRESH: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    pub members: BTreeMap<u64, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    pub global_score: u64,
+    /// Seconds since the unix epoch, zero before the first star
+    pub last_star_ts: u64,
+    /// Stars by day then part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    /// Seconds since the unix epoch
+    pub get_star_ts: u64,
+    pub star_index: u64,
+}
+
+impl Leaderboard {
+    /// Members by local score, ties going to whoever reached it first.
+    pub fn standings(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+}
+
+impl Member {
+    /// The member's name, anonymous members are shown the way the puzzle site shows them.
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// When the member got the star for `part` of `day`.
+    pub fn star(&self, day: u8, part: u8) -> Option<SystemTime> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        Some(UNIX_EPOCH + Duration::from_secs(star.get_star_ts))
+    }
+}
+
+impl Input {
+    /// The private leaderboard `id` for `year`, refetched only once the cached copy is older than
+    /// `LEADERBOARD_REFRESH`.
+    pub fn leaderboard(&self, year: u16, id: u64) -> Result<Leaderboard, InputError> {
+        let path = self.cache_path.join(year.to_string()).join("leaderboard").join(format!("{}.json", id));
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let fresh = Metadata::read(&path)?.is_some_and(|meta| now < Duration::from_secs(meta.fetched) + LEADERBOARD_REFRESH);
+        if fresh || self.client.offline {
+            match read_cache(&path)? {
+                Some(cached) => return serde_json::from_str(&cached).map_err(InputError::Leaderboard),
+                None if self.client.offline => return Err(InputError::Offline),
+                None => {}
+            }
+        }
+
+        let url = self.client.year_url(year, &["leaderboard", "private", "view", &format!("{}.json", id)]);
+        let body = self.client.fetch(url.clone())?;
+        // A missing or expired session is redirected to an html login page
+        if let Some(reason) = unexpected_body(&body) {
+            return Err(InputError::UnexpectedBody { url: url.to_string(), reason });
+        }
+        let leaderboard = serde_json::from_str(&body).map_err(InputError::Leaderboard)?;
+        write_verified(&path, &body, url.as_str())?;
+        Ok(leaderboard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    const LEADERBOARD: &str = r#"{"owner_id":1,"event":"2022","members":{
+        "1":{"id":1,"name":"alice","stars":3,"local_score":7,"global_score":0,"last_star_ts":1669871400,
+             "completion_day_level":{"1":{"1":{"get_star_ts":1669871100,"star_index":10},"2":{"get_star_ts":1669871400,"star_index":20}},
+                                     "2":{"1":{"get_star_ts":1669957800,"star_index":30}}}},
+        "2":{"id":2,"name":null,"stars":0,"local_score":0,"global_score":0,"last_star_ts":0,"completion_day_level":{}}}}"#;
+
+    #[test]
+    fn test_leaderboard() {
+        let cache_path = mock::TempDir::new("leaderboard");
+        let (url, server) = mock::serve(&[(200, LEADERBOARD)]);
+        let input = mock::input(&cache_path, url);
+        let leaderboard = input.leaderboard(2022, 1).unwrap();
+        // Within the refresh interval the cached copy is used, the mock only answers once
+        input.leaderboard(2022, 1).unwrap();
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].path, "/2022/leaderboard/private/view/1.json");
+        assert_eq!(requests[0].header("cookie"), Some("session=token"));
+
+        let standings = leaderboard.standings();
+        assert_eq!(
+            standings.iter().map(|member| member.display_name()).collect::<Vec<_>>(),
+            vec!["alice", "(anonymous user #2)"]
+        );
+        assert_eq!(standings[0].star(1, 2), Some(UNIX_EPOCH + Duration::from_secs(1669871400)));
+        assert_eq!(standings[0].star(2, 2), None);
+    }
+}
@@ -1,10 +1,35 @@
-use config::builder::DefaultState;
-use config::{ConfigBuilder, File};
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
 use thiserror::Error;
 use url::Url;
 
+mod answer;
+mod cache;
+mod client;
+mod examples;
+mod html;
+mod leaderboard;
+mod maintenance;
+#[cfg(test)]
+mod mock;
+mod puzzle;
+mod schedule;
+mod session;
+mod settings;
+mod sources;
+mod submit;
+
+pub use cache::Metadata;
+pub(crate) use cache::{read_cache, write_cache};
+pub use client::{default_user_agent, Client};
+pub use leaderboard::{Leaderboard, Member, Star, LEADERBOARD_REFRESH};
+pub use maintenance::{CacheEntry, CacheStatus, Prefetch};
+pub use schedule::{format_utc, is_unlocked, unlocks_at};
+pub use settings::{Overrides, Setting, Settings, Source};
+pub use sources::{ChainSource, DirectorySource, HttpSource, InputSource, MemorySource, StdinSource};
+pub use submit::Submission;
+
 #[derive(Debug, Error)]
 pub enum InputError {
     #[error("failed to load configuration file")]
@@ -17,61 +42,173 @@ pub enum InputError {
     Caching { source: std::io::Error, path: String },
     #[error("get failed with {status}")]
     GetFailed { status: String },
+    #[error("error submitting answer")]
+    Submit(#[source] attohttpc::Error),
+    #[error("unexpected response from server")]
+    UnexpectedResponse,
+    #[error("failed to read cached data at {path}")]
+    ReadCache { source: std::io::Error, path: String },
+    #[error("day {day} is not cached at {path} and network access is disabled")]
+    NotCached { day: u8, path: String },
+    #[error("no input for day {day} from {origin}")]
+    NoInput { day: u8, origin: String },
+    #[error("network access is disabled")]
+    Offline,
+    #[error("failed to read session file {path}")]
+    ReadSessionFile { source: std::io::Error, path: String },
+    #[error("missing required setting {name}")]
+    MissingSetting { name: &'static str },
+    #[error("invalid value {value:?} for {name}")]
+    InvalidSetting { name: &'static str, value: String },
+    #[error("refusing to cache response from {url}: {reason}")]
+    UnexpectedBody { url: String, reason: &'static str },
+    #[error("cached data at {path} does not match its metadata, delete it to refetch")]
+    CorruptCache { path: String },
+    #[error("invalid cache metadata at {path}")]
+    InvalidMetadata { path: String },
+    #[error("session has expired, log in again and update the session token")]
+    SessionExpired,
+    #[error("session token was rejected with {status}")]
+    Unauthorized { status: String },
+    #[error("puzzle is not unlocked until {}", format_time(.unlocks_at))]
+    NotYetUnlocked { unlocks_at: SystemTime },
+    #[error("failed to parse leaderboard")]
+    Leaderboard(#[source] serde_json::Error),
+}
+
+fn format_time(time: &SystemTime) -> String {
+    format_utc(time.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs())
+}
+
+fn base_url(url: &str) -> Result<Url, InputError> {
+    let mut url = Url::parse(url).map_err(InputError::ParseUrl)?;
+    // Older configs pointed the url at a single event, the year is now part of every request
+    if url
+        .path_segments()
+        .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+        .is_some_and(|s| s.parse::<u16>().is_ok())
+    {
+        url.path_segments_mut().expect("Is base URL").pop_if_empty().pop();
+    }
+    Ok(url)
 }
 
 pub struct Input {
     cache_path: PathBuf,
-    url: Url,
-    session: String,
+    client: Arc<Client>,
+    source: Box<dyn InputSource>,
 }
 
 impl Input {
     pub fn open<P: AsRef<Path>>(config: P) -> Result<Self, InputError> {
-        let config = ConfigBuilder::<DefaultState>::default()
-            .add_source(File::from(config.as_ref()))
-            .build()
-            .map_err(InputError::Configuration)?;
-        let cache_path = config.get_string("cache_path").map_err(InputError::Configuration)?;
-        let url = config.get_string("url").map_err(InputError::Configuration)?;
-        let session = config.get("session").map_err(InputError::Configuration)?;
-
-        Ok(Self {
-            cache_path: PathBuf::from(cache_path),
-            session,
-            url: Url::parse(&url).map_err(InputError::ParseUrl)?,
-        })
+        Ok(Self::new(&Settings::load(Some(config.as_ref()), &Overrides::default())?))
+    }
+
+    pub fn new(settings: &Settings) -> Self {
+        Self::with_client(settings.input_cache_path(), Arc::new(Client::new(settings)))
     }
 
-    pub fn get(&self, day: u8) -> Result<String, InputError> {
-        let day = day.to_string();
-        let input_path = self.cache_path.join(Path::new(&day)).join("input");
-        let dir_path = self.cache_path.join(Path::new(&day));
-        match fs::read_to_string(&input_path) {
-            Ok(input) => Ok(input),
-            Err(_) => {
-                let mut new_url = self.url.clone();
-                new_url.path_segments_mut().expect("Is base URL").extend(&["day", &day, "input"]);
-                let input = attohttpc::get(new_url.as_str())
-                    .header_append(attohttpc::header::COOKIE, format!("session={}", &self.session))
-                    .send()
-                    .map_err(InputError::HttpGet)?;
-                if input.is_success() {
-                    let input = input.text().map_err(InputError::HttpGet)?;
-                    fs::create_dir_all(&dir_path).map_err(|e| InputError::Caching {
-                        source: e,
-                        path: dir_path.to_string_lossy().to_string(),
-                    })?;
-                    fs::write(&input_path, &input).map_err(|e| InputError::Caching {
-                        source: e,
-                        path: input_path.to_string_lossy().to_string(),
-                    })?;
-                    Ok(input)
-                } else {
-                    Err(InputError::GetFailed {
-                        status: input.status().to_string(),
-                    })
-                }
-            }
+    /// Inputs come from the cache directory first, then the puzzle site.
+    pub fn with_client(cache_path: PathBuf, client: Arc<Client>) -> Self {
+        let source = ChainSource::new(vec![Box::new(DirectorySource::new(&cache_path)), Box::new(HttpSource::new(client.clone()))]);
+        Self {
+            cache_path,
+            client,
+            source: Box::new(source),
         }
     }
+
+    /// Replaces where `get` looks for inputs, the rest of `Input` keeps using the cache and client.
+    pub fn set_source(&mut self, source: Box<dyn InputSource>) {
+        self.source = source;
+    }
+
+    fn day_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_path.join(year.to_string()).join(day.to_string())
+    }
+
+    fn not_cached(&self, day: u8, path: &Path) -> InputError {
+        InputError::NotCached {
+            day,
+            path: path.to_string_lossy().to_string(),
+        }
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Result<String, InputError> {
+        match self.source.get(year, day) {
+            Ok(Some(input)) => Ok(input),
+            Ok(None) if !self.client.offline => Err(InputError::NoInput {
+                day,
+                origin: self.source.origin(year, day),
+            }),
+            Ok(None) | Err(InputError::Offline) => Err(self.not_cached(day, &self.day_path(year, day).join("input"))),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use std::fs;
+
+    #[test]
+    fn test_base_url() {
+        assert_eq!(base_url("https://adventofcode.com/").unwrap().as_str(), "https://adventofcode.com/");
+        assert_eq!(base_url("https://adventofcode.com/2022").unwrap().as_str(), "https://adventofcode.com/");
+        assert_eq!(base_url("https://adventofcode.com/2022/").unwrap().as_str(), "https://adventofcode.com/");
+    }
+
+    #[test]
+    fn test_get_caches_by_year() {
+        let cache_path = mock::TempDir::new("get");
+        let (url, server) = mock::serve(&[(200, "1\n2\n")]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.get(2022, 1).unwrap(), "1\n2\n");
+        // Served from the cache, the mock only answers once
+        assert_eq!(input.get(2022, 1).unwrap(), "1\n2\n");
+        assert!(cache_path.join("2022").join("1").join("input").exists());
+        assert_eq!(Metadata::read(&cache_path.join("2022").join("1").join("input")).unwrap().unwrap().length, 4);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+    }
+
+    #[test]
+    fn test_html_not_cached() {
+        let cache_path = mock::TempDir::new("html");
+        let (url, server) = mock::serve(&[(200, "<!DOCTYPE html>\n<html><body>Log in</body></html>")]);
+        let input = mock::input(&cache_path, url);
+        assert!(matches!(input.get(2022, 1), Err(InputError::UnexpectedBody { .. })));
+        assert!(!cache_path.join("2022").join("1").join("input").exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_not_yet_unlocked() {
+        let cache_path = mock::TempDir::new("locked");
+        let input = mock::input(&cache_path, Url::parse("http://127.0.0.1:9/").unwrap());
+        let error = input.get(9999, 1).unwrap_err();
+        assert!(matches!(error, InputError::NotYetUnlocked { unlocks_at } if unlocks_at == schedule::unlocks_at(9999, 1)));
+        assert_eq!(error.to_string(), "puzzle is not unlocked until 9999-12-01 05:00:00 UTC");
+        assert!(!cache_path.exists());
+    }
+
+    #[test]
+    fn test_offline() {
+        let cache_path = mock::TempDir::new("offline");
+        let url = Url::parse("http://127.0.0.1:9/").unwrap();
+        let mut client = mock::client(&cache_path, url.clone());
+        client.offline = true;
+        let input = mock::input_with(&cache_path, client);
+        assert!(matches!(input.get(2022, 1), Err(InputError::NotCached { day: 1, .. })));
+        assert!(matches!(input.submit(2022, 1, 1, "1"), Err(InputError::Offline)));
+
+        // A read failure other than a missing file is reported instead of refetching
+        fs::create_dir_all(cache_path.join("2022").join("2").join("input")).unwrap();
+        let input = mock::input(&cache_path, url);
+        assert!(matches!(input.get(2022, 2), Err(InputError::ReadCache { .. })));
+    }
 }
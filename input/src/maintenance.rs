@@ -0,0 +1,154 @@
+use crate::cache::{read_cache, write_verified};
+use crate::schedule::is_unlocked;
+use crate::{HttpSource, Input, InputError, InputSource, Metadata};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// Files fetched from the puzzle site, recorded answers are kept by `purge`.
+const FETCHED: [&str; 5] = ["input", "input.meta", "puzzle.html", "puzzle.html.meta", "examples"];
+
+/// A cached input as found on disk.
+#[derive(Debug)]
+pub struct CacheEntry {
+    pub day: u8,
+    pub path: PathBuf,
+    pub size: u64,
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Ok,
+    /// Cached before metadata was recorded, so there is nothing to check against
+    Unverified,
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefetch {
+    Cached,
+    Downloaded,
+    Locked,
+}
+
+impl Input {
+    /// Every day of `year` with a cached input, in day order.
+    pub fn cached(&self, year: u16) -> Result<Vec<CacheEntry>, InputError> {
+        let mut entries = Vec::new();
+        for day in 1..=25 {
+            let path = self.day_path(year, day).join("input");
+            match fs::metadata(&path) {
+                Ok(file) => entries.push(CacheEntry {
+                    day,
+                    size: file.len(),
+                    metadata: Metadata::read(&path)?,
+                    path,
+                }),
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(InputError::ReadCache {
+                        source: e,
+                        path: path.to_string_lossy().to_string(),
+                    })
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Checks each cached input of `year` against its recorded length and hash.
+    pub fn verify_cache(&self, year: u16) -> Result<Vec<(u8, CacheStatus)>, InputError> {
+        self.cached(year)?
+            .into_iter()
+            .map(|entry| {
+                let contents = read_cache(&entry.path)?.unwrap_or_default();
+                let status = match entry.metadata {
+                    Some(meta) if meta.matches(&contents) => CacheStatus::Ok,
+                    Some(_) => CacheStatus::Corrupt,
+                    None => CacheStatus::Unverified,
+                };
+                Ok((entry.day, status))
+            })
+            .collect()
+    }
+
+    /// Downloads the input for `day` into the cache unless it is already there or not unlocked yet.
+    pub fn prefetch(&self, year: u16, day: u8) -> Result<Prefetch, InputError> {
+        let path = self.day_path(year, day).join("input");
+        if path.exists() {
+            return Ok(Prefetch::Cached);
+        }
+        if !is_unlocked(year, day) {
+            return Ok(Prefetch::Locked);
+        }
+        let source = HttpSource::new(self.client.clone());
+        let input = source.get(year, day)?.unwrap_or_default();
+        write_verified(&path, &input, &source.origin(year, day))?;
+        Ok(Prefetch::Downloaded)
+    }
+
+    /// Removes the fetched files for `day`, or every day of `year` when `None`, returning how many days had any.
+    pub fn purge(&self, year: u16, day: Option<u8>) -> Result<usize, InputError> {
+        let days = match day {
+            Some(day) => day..=day,
+            None => 1..=25,
+        };
+        let mut purged = 0;
+        for day in days {
+            let day_path = self.day_path(year, day);
+            let mut removed = false;
+            for name in FETCHED {
+                let path = day_path.join(name);
+                let result = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+                match result {
+                    Ok(()) => removed = true,
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => {
+                        return Err(InputError::Caching {
+                            source: e,
+                            path: path.to_string_lossy().to_string(),
+                        })
+                    }
+                }
+            }
+            // Leave the directory if answers were recorded in it
+            let _ = fs::remove_dir(&day_path);
+            purged += removed as usize;
+        }
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    #[test]
+    fn test_maintenance() {
+        let cache_path = mock::TempDir::new("maintenance");
+        let (url, server) = mock::serve(&[(200, "1\n2\n")]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.prefetch(2022, 1).unwrap(), Prefetch::Downloaded);
+        assert_eq!(input.prefetch(2022, 1).unwrap(), Prefetch::Cached);
+        assert_eq!(input.prefetch(9999, 1).unwrap(), Prefetch::Locked);
+        server.join().unwrap();
+
+        fs::create_dir_all(cache_path.join("2022").join("2")).unwrap();
+        fs::write(cache_path.join("2022").join("2").join("input"), "legacy\n").unwrap();
+        input.record_answer(2022, 2, 1, "7").unwrap();
+        let cached = input.cached(2022).unwrap();
+        assert_eq!(cached.iter().map(|entry| (entry.day, entry.size)).collect::<Vec<_>>(), vec![(1, 4), (2, 7)]);
+        assert_eq!(input.verify_cache(2022).unwrap(), vec![(1, CacheStatus::Ok), (2, CacheStatus::Unverified)]);
+
+        fs::write(cache_path.join("2022").join("1").join("input"), "1\n").unwrap();
+        assert_eq!(input.verify_cache(2022).unwrap()[0], (1, CacheStatus::Corrupt));
+
+        assert_eq!(input.purge(2022, Some(1)).unwrap(), 1);
+        assert!(!cache_path.join("2022").join("1").exists());
+        assert_eq!(input.purge(2022, None).unwrap(), 1);
+        assert_eq!(input.answer(2022, 2, 1).unwrap().as_deref(), Some("7"));
+        assert!(input.cached(2022).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{default_user_agent, Client, Input};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use std::{env, fs, process};
+use url::Url;
+
+/// A directory under the system temp dir, removed again when dropped so a failing test doesn't leave it behind.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` keeps tests apart, the process id keeps concurrent runs apart.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+        // Left over from a run that was killed before it could clean up
+        let _ = fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+pub fn client(cache_path: &Path, url: Url) -> Client {
+    Client {
+        url,
+        session: "token".to_string(),
+        offline: false,
+        user_agent: default_user_agent(),
+        min_interval: Duration::ZERO,
+        state_path: cache_path.to_path_buf(),
+        request_lock: Mutex::new(()),
+    }
+}
+
+pub fn input_with(cache_path: &Path, client: Client) -> Input {
+    Input::with_client(cache_path.to_path_buf(), Arc::new(client))
+}
+
+pub fn input(cache_path: &Path, url: Url) -> Input {
+    input_with(cache_path, client(cache_path, url))
+}
+
+#[derive(Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Serves one canned `(status, body)` response per incoming connection, in order, and returns the
+/// requests it received once every response has been sent.
+pub fn serve(responses: &[(u16, &str)]) -> (Url, JoinHandle<Vec<Request>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().expect("local addr"))).expect("mock url");
+    let responses = responses.iter().map(|(status, body)| (*status, body.to_string())).collect::<Vec<_>>();
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("request line");
+            let mut parts = line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("header");
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((key, value)) = line.split_once(':') {
+                    headers.push((key.trim().to_string(), value.trim().to_string()));
+                }
+            }
+            let mut request = Request {
+                method,
+                path,
+                headers,
+                body: String::new(),
+            };
+            let length = request.header("content-length").and_then(|len| len.parse().ok()).unwrap_or(0);
+            let mut buf = vec![0; length];
+            reader.read_exact(&mut buf).expect("body");
+            request.body = String::from_utf8_lossy(&buf).to_string();
+            requests.push(request);
+
+            write!(
+                stream,
+                "HTTP/1.1 {} Mock\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .expect("write response");
+        }
+        requests
+    });
+    (url, handle)
+}
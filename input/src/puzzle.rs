@@ -0,0 +1,95 @@
+use crate::cache::{read_cache, write_verified};
+use crate::{html, Input, InputError, Metadata};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a puzzle page without part two is trusted before it is fetched again.
+const PUZZLE_REFRESH: Duration = Duration::from_secs(60 * 60);
+
+impl Input {
+    /// Puzzle description for `day` as markdown, with part two included once it is unlocked. Served from the
+    /// cached page under the same refresh rules as `puzzle_page`, so describing an unsolved day doesn't refetch it.
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let (page, _) = self.puzzle_page(year, day)?;
+        Ok(html::articles(&page).into_iter().map(html::to_markdown).collect::<Vec<_>>().join("\n\n"))
+    }
+
+    pub(crate) fn puzzle_path(&self, year: u16, day: u8) -> PathBuf {
+        self.day_path(year, day).join("puzzle.html")
+    }
+
+    /// Fetches the puzzle page for `day` and caches it along with when it was fetched.
+    pub(crate) fn fetch_puzzle_page(&self, year: u16, day: u8) -> Result<String, InputError> {
+        let page = self.client.fetch_day(year, day, None)?;
+        write_verified(&self.puzzle_path(year, day), &page, self.client.day_url(year, day, None).as_str())?;
+        Ok(page)
+    }
+
+    /// The cached puzzle page for `day`, and whether it was just fetched. Part two only shows up after part one
+    /// is solved, so a page without it is refreshed once part one's answer is recorded or the copy is older than
+    /// `PUZZLE_REFRESH`. A failed refresh falls back to the cached copy.
+    pub(crate) fn puzzle_page(&self, year: u16, day: u8) -> Result<(String, bool), InputError> {
+        let page_path = self.puzzle_path(year, day);
+        let Some(page) = read_cache(&page_path)? else {
+            if self.client.offline {
+                return Err(self.not_cached(day, &page_path));
+            }
+            return Ok((self.fetch_puzzle_page(year, day)?, true));
+        };
+        if self.client.offline || html::articles(&page).len() >= 2 || !self.puzzle_outdated(year, day)? {
+            return Ok((page, false));
+        }
+        match self.fetch_puzzle_page(year, day) {
+            Ok(page) => Ok((page, true)),
+            Err(_) => Ok((page, false)),
+        }
+    }
+
+    fn puzzle_outdated(&self, year: u16, day: u8) -> Result<bool, InputError> {
+        // Pages cached before their fetch time was recorded count as outdated
+        let Some(meta) = Metadata::read(&self.puzzle_path(year, day))? else {
+            return Ok(true);
+        };
+        let fetched = UNIX_EPOCH + Duration::from_secs(meta.fetched);
+        let answered = fs::metadata(self.answer_path(year, day, 1)).and_then(|answer| answer.modified());
+        Ok(answered.is_ok_and(|answered| answered > fetched) || SystemTime::now() > fetched + PUZZLE_REFRESH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock;
+
+    #[test]
+    fn test_puzzle_refreshes_until_part_two() {
+        let cache_path = mock::TempDir::new("puzzle");
+        let part1 = "<main><article><h2>--- Day 1 ---</h2><p>One</p></article></main>";
+        let both = "<main><article><h2>--- Day 1 ---</h2><p>One</p></article><article><h2>--- Part Two ---</h2><p>Two</p></article></main>";
+        let (url, server) = mock::serve(&[(200, part1), (200, both)]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.puzzle(2022, 1).unwrap(), "## --- Day 1 ---\n\nOne");
+        input.record_answer(2022, 1, 1, "1").unwrap();
+        let expected = "## --- Day 1 ---\n\nOne\n\n## --- Part Two ---\n\nTwo";
+        assert_eq!(input.puzzle(2022, 1).unwrap(), expected);
+        // Both parts cached, no further requests
+        assert_eq!(input.puzzle(2022, 1).unwrap(), expected);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].path, "/2022/day/1");
+    }
+
+    #[test]
+    fn test_puzzle_falls_back_to_cache() {
+        let cache_path = mock::TempDir::new("puzzle-stale");
+        let day_path = cache_path.join("2022").join("1");
+        std::fs::create_dir_all(&day_path).unwrap();
+        // Cached without a fetch time, so it is due for a refresh
+        std::fs::write(day_path.join("puzzle.html"), "<main><article><p>One</p></article></main>").unwrap();
+        let (url, server) = mock::serve(&[(500, "oops")]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.puzzle(2022, 1).unwrap(), "One");
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+}
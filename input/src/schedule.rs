@@ -0,0 +1,68 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight US Eastern, which is 05:00 UTC during December.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// Days since the unix epoch for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// `(year, month, day)` for a count of days since the unix epoch.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if month <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 }, month, day)
+}
+
+/// When the puzzle for `day` of `year` becomes available.
+pub fn unlocks_at(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as u32) as u64;
+    UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+pub fn is_unlocked(year: u16, day: u8) -> bool {
+    SystemTime::now() >= unlocks_at(year, day)
+}
+
+/// Formats seconds since the unix epoch as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn format_utc(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86400) as i64);
+    let time = secs % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlocks_at() {
+        let secs = unlocks_at(2022, 1).duration_since(UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(secs, 1669870800);
+        assert_eq!(format_utc(secs), "2022-12-01 05:00:00 UTC");
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_utc(951782400), "2000-02-29 00:00:00 UTC");
+        assert!(is_unlocked(2022, 25));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{Input, InputError};
+
+impl Input {
+    /// Verifies the session token by requesting a page that is only served to logged in users. The puzzle
+    /// site redirects expired sessions to the login page and rejects malformed ones outright.
+    pub fn check_session(&self) -> Result<(), InputError> {
+        if self.client.offline {
+            return Err(InputError::Offline);
+        }
+        let url = self.client.url.join("settings")?;
+        let response = self.client.send(attohttpc::get(url.as_str()).follow_redirects(false), InputError::HttpGet)?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_redirection() {
+            Err(InputError::SessionExpired)
+        } else if status.is_client_error() {
+            Err(InputError::Unauthorized { status: status.to_string() })
+        } else {
+            Err(InputError::GetFailed { status: status.to_string() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{mock, InputError};
+
+    #[test]
+    fn test_check_session() {
+        let cache_path = mock::TempDir::new("session");
+        let (url, server) = mock::serve(&[(200, "<html>settings</html>"), (302, ""), (400, "bad"), (500, "oops")]);
+        let input = mock::input(&cache_path, url);
+        assert!(input.check_session().is_ok());
+        assert!(matches!(input.check_session(), Err(InputError::SessionExpired)));
+        assert!(matches!(input.check_session(), Err(InputError::Unauthorized { .. })));
+        assert!(matches!(input.check_session(), Err(InputError::GetFailed { .. })));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].path, "/settings");
+        assert_eq!(requests[0].header("cookie"), Some("session=token"));
+    }
+
+    #[test]
+    fn test_input_logged_out() {
+        let cache_path = mock::TempDir::new("logged-out");
+        let (url, server) = mock::serve(&[(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")]);
+        let input = mock::input(&cache_path, url);
+        assert!(matches!(input.get(2022, 1), Err(InputError::SessionExpired)));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,552 @@
+use crate::{base_url, default_user_agent, InputError};
+use config::builder::DefaultState;
+use config::{Config, ConfigBuilder, ConfigError, File};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use url::Url;
+
+const DEFAULT_CACHE_PATH: &str = "cache";
+const DEFAULT_URL: &str = "https://adventofcode.com/";
+const DEFAULT_MIN_INTERVAL: f64 = 5.0;
+
+/// Where a setting's value came from, lowest precedence first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    ConfigFile(PathBuf),
+    Environment(&'static str),
+    CommandLine,
+    SessionFile { path: PathBuf, via: Box<Source> },
+    Profile { name: String, path: PathBuf },
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::ConfigFile(path) => write!(f, "config file {}", path.display()),
+            Source::Environment(var) => write!(f, "environment {}", var),
+            Source::CommandLine => write!(f, "command line"),
+            Source::SessionFile { path, via } => write!(f, "session file {} (from {})", path.display(), via),
+            Source::Profile { name, path } => write!(f, "profile {} in config file {}", name, path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+/// Values given on the command line, these take precedence over every other source except a selected profile's
+/// own session.
+#[derive(Debug, Default, Clone)]
+pub struct Overrides {
+    pub cache_path: Option<PathBuf>,
+    pub url: Option<String>,
+    pub session: Option<String>,
+    pub session_file: Option<PathBuf>,
+    pub offline: Option<bool>,
+    pub profile: Option<String>,
+}
+
+/// A single layer of raw values before they are merged.
+struct Layer {
+    cache_path: Option<String>,
+    url: Option<String>,
+    session: Option<String>,
+    session_file: Option<String>,
+    offline: Option<bool>,
+    user_agent: Option<String>,
+    min_interval: Option<f64>,
+    profile: Option<String>,
+}
+
+enum SessionValue {
+    Token(String),
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub cache_path: Setting<PathBuf>,
+    pub url: Setting<Url>,
+    pub session: Setting<String>,
+    pub offline: Setting<bool>,
+    pub user_agent: Setting<String>,
+    pub min_interval: Setting<Duration>,
+    /// The selected profile, its inputs are cached in `profile_dir` below `cache_path`
+    pub profile: Option<Setting<String>>,
+    pub profile_dir: Option<PathBuf>,
+    /// Every profile defined in the config file
+    pub profiles: Vec<String>,
+}
+
+fn optional<T>(value: Result<T, ConfigError>) -> Result<Option<T>, InputError> {
+    match value {
+        Ok(value) => Ok(Some(value)),
+        Err(ConfigError::NotFound(_)) => Ok(None),
+        Err(e) => Err(InputError::Configuration(e)),
+    }
+}
+
+/// Settings under `prefix`, either the top level of the config file or a `[profiles.<name>]` table.
+fn config_layer(config: &Config, prefix: &str) -> Result<Layer, InputError> {
+    let key = |name: &str| format!("{}{}", prefix, name);
+    Ok(Layer {
+        cache_path: optional(config.get_string(&key("cache_path")))?,
+        url: optional(config.get_string(&key("url")))?,
+        session: optional(config.get_string(&key("session")))?,
+        session_file: optional(config.get_string(&key("session_file")))?,
+        offline: optional(config.get_bool(&key("offline")))?,
+        user_agent: optional(config.get_string(&key("user_agent")))?,
+        min_interval: optional(config.get_float(&key("min_interval")))?,
+        profile: None,
+    })
+}
+
+fn load_config(path: &Path, required: bool) -> Result<Config, InputError> {
+    ConfigBuilder::<DefaultState>::default()
+        .add_source(File::from(path).required(required))
+        .build()
+        .map_err(InputError::Configuration)
+}
+
+/// An explicitly named config file must exist, the default one is optional.
+fn config_path(config: Option<&Path>) -> (PathBuf, bool) {
+    match config {
+        Some(path) => (path.to_path_buf(), true),
+        None => (PathBuf::from("config.toml"), false),
+    }
+}
+
+fn profile_names(config: &Config) -> Result<Vec<String>, InputError> {
+    let mut profiles = optional(config.get_table("profiles"))?.unwrap_or_default().into_keys().collect::<Vec<_>>();
+    profiles.sort();
+    Ok(profiles)
+}
+
+fn env_layer(env: &dyn Fn(&str) -> Option<String>) -> Result<Layer, InputError> {
+    let offline = match env("AOC_OFFLINE") {
+        Some(offline) => Some(match offline.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" | "" => false,
+            _ => {
+                return Err(InputError::InvalidSetting {
+                    name: "AOC_OFFLINE",
+                    value: offline,
+                })
+            }
+        }),
+        None => None,
+    };
+    let min_interval = match env("AOC_MIN_INTERVAL") {
+        Some(interval) => Some(interval.parse().map_err(|_| InputError::InvalidSetting {
+            name: "AOC_MIN_INTERVAL",
+            value: interval,
+        })?),
+        None => None,
+    };
+    Ok(Layer {
+        cache_path: env("AOC_CACHE_PATH"),
+        url: env("AOC_URL"),
+        session: env("AOC_SESSION"),
+        session_file: env("AOC_SESSION_FILE"),
+        offline,
+        user_agent: env("AOC_USER_AGENT"),
+        min_interval,
+        profile: env("AOC_PROFILE"),
+    })
+}
+
+fn read_session_file(path: PathBuf, via: Source) -> Result<Setting<String>, InputError> {
+    let session = fs::read_to_string(&path).map_err(|e| InputError::ReadSessionFile {
+        source: e,
+        path: path.to_string_lossy().to_string(),
+    })?;
+    Ok(Setting {
+        value: session.trim().to_string(),
+        source: Source::SessionFile { path, via: Box::new(via) },
+    })
+}
+
+impl Settings {
+    /// Merges defaults, the config file, `AOC_*` environment variables and `overrides`, in that order. A selected
+    /// profile's session belongs to its account, so it wins over any other session.
+    pub fn load(config: Option<&Path>, overrides: &Overrides) -> Result<Self, InputError> {
+        Self::load_with(config, overrides, &|var| std::env::var(var).ok())
+    }
+
+    /// The profiles defined in the config file, without loading any settings. The top level of a config file
+    /// that keeps every session under `[profiles]` has no session of its own.
+    pub fn profiles(config: Option<&Path>) -> Result<Vec<String>, InputError> {
+        let (config_path, required) = config_path(config);
+        profile_names(&load_config(&config_path, required)?)
+    }
+
+    fn load_with(config: Option<&Path>, overrides: &Overrides, env: &dyn Fn(&str) -> Option<String>) -> Result<Self, InputError> {
+        let (config_path, required) = config_path(config);
+        let cli = Layer {
+            cache_path: overrides.cache_path.as_ref().map(|path| path.to_string_lossy().to_string()),
+            url: overrides.url.clone(),
+            session: overrides.session.clone(),
+            session_file: overrides.session_file.as_ref().map(|path| path.to_string_lossy().to_string()),
+            offline: overrides.offline,
+            user_agent: None,
+            min_interval: None,
+            profile: overrides.profile.clone(),
+        };
+        let config = load_config(&config_path, required)?;
+        let mut file = config_layer(&config, "")?;
+        file.profile = optional(config.get_string("profile"))?;
+        let mut layers = vec![
+            (Source::ConfigFile(config_path.clone()), file),
+            (Source::Environment("AOC_*"), env_layer(env)?),
+            (Source::CommandLine, cli),
+        ];
+
+        let profiles = profile_names(&config)?;
+        // The selected profile's table sits just above the top level of the config file
+        let mut profile = None;
+        for (source, layer) in &layers {
+            if let Some(name) = &layer.profile {
+                let source = match source {
+                    Source::Environment(_) => Source::Environment("AOC_PROFILE"),
+                    source => source.clone(),
+                };
+                profile = Some(Setting { value: name.clone(), source });
+            }
+        }
+        let mut profile_dir = None;
+        let mut profile_session = None;
+        if let Some(Setting { value: name, .. }) = &profile {
+            if !profiles.contains(name) {
+                return Err(InputError::InvalidSetting {
+                    name: "profile",
+                    value: name.clone(),
+                });
+            }
+            let prefix = format!("profiles.{}.", name);
+            let dir = optional(config.get_string(&format!("{}cache_dir", prefix)))?.unwrap_or_else(|| name.clone());
+            profile_dir = Some(PathBuf::from(dir));
+            let source = Source::Profile {
+                name: name.clone(),
+                path: config_path.clone(),
+            };
+            let layer = config_layer(&config, &prefix)?;
+            // Kept aside so it isn't replaced by a session from the environment or command line
+            if let Some(value) = &layer.session {
+                profile_session = Some((SessionValue::Token(value.clone()), source.clone()));
+            } else if let Some(path) = &layer.session_file {
+                profile_session = Some((SessionValue::File(PathBuf::from(path)), source.clone()));
+            }
+            layers.insert(1, (source, layer));
+        }
+
+        let mut cache_path = Setting {
+            value: DEFAULT_CACHE_PATH.to_string(),
+            source: Source::Default,
+        };
+        let mut url = Setting {
+            value: DEFAULT_URL.to_string(),
+            source: Source::Default,
+        };
+        let mut offline = Setting {
+            value: false,
+            source: Source::Default,
+        };
+        let mut user_agent = Setting {
+            value: default_user_agent(),
+            source: Source::Default,
+        };
+        let mut min_interval = Setting {
+            value: DEFAULT_MIN_INTERVAL,
+            source: Source::Default,
+        };
+        let mut session = None;
+        for (source, layer) in layers {
+            let source_for = |var: &'static str| match &source {
+                Source::Environment(_) => Source::Environment(var),
+                source => source.clone(),
+            };
+            if let Some(value) = layer.cache_path {
+                cache_path = Setting {
+                    value,
+                    source: source_for("AOC_CACHE_PATH"),
+                };
+            }
+            if let Some(value) = layer.url {
+                url = Setting {
+                    value,
+                    source: source_for("AOC_URL"),
+                };
+            }
+            if let Some(value) = layer.offline {
+                offline = Setting {
+                    value,
+                    source: source_for("AOC_OFFLINE"),
+                };
+            }
+            if let Some(value) = layer.user_agent {
+                user_agent = Setting {
+                    value,
+                    source: source_for("AOC_USER_AGENT"),
+                };
+            }
+            if let Some(value) = layer.min_interval {
+                min_interval = Setting {
+                    value,
+                    source: source_for("AOC_MIN_INTERVAL"),
+                };
+            }
+            // A session given directly wins over a session file from the same source, files are only
+            // read once we know which one is used
+            if let Some(value) = layer.session {
+                session = Some((SessionValue::Token(value), source_for("AOC_SESSION")));
+            } else if let Some(path) = layer.session_file {
+                session = Some((SessionValue::File(PathBuf::from(path)), source_for("AOC_SESSION_FILE")));
+            }
+        }
+        let session = match profile_session.or(session) {
+            Some((SessionValue::Token(value), source)) => Setting { value, source },
+            Some((SessionValue::File(path), source)) => read_session_file(path, source)?,
+            None => return Err(InputError::MissingSetting { name: "session" }),
+        };
+
+        Ok(Self {
+            cache_path: Setting {
+                value: PathBuf::from(cache_path.value),
+                source: cache_path.source,
+            },
+            url: Setting {
+                value: base_url(&url.value)?,
+                source: url.source,
+            },
+            session,
+            offline,
+            user_agent,
+            min_interval: Setting {
+                value: Duration::try_from_secs_f64(min_interval.value).map_err(|_| InputError::InvalidSetting {
+                    name: "min_interval",
+                    value: min_interval.value.to_string(),
+                })?,
+                source: min_interval.source,
+            },
+            profile,
+            profile_dir,
+            profiles,
+        })
+    }
+
+    /// Where inputs for the selected profile are cached.
+    pub fn input_cache_path(&self) -> PathBuf {
+        match &self.profile_dir {
+            Some(dir) => self.cache_path.value.join(dir),
+            None => self.cache_path.value.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let session = format!("<{} characters>", self.session.value.len());
+        let profile = match &self.profile {
+            Some(profile) => (profile.value.clone(), &profile.source),
+            None => ("none".to_string(), &Source::Default),
+        };
+        let min_interval = format!("{:?}", self.min_interval.value);
+        let rows = [
+            ("cache_path", self.cache_path.value.display().to_string(), &self.cache_path.source),
+            ("url", self.url.value.to_string(), &self.url.source),
+            ("session", session, &self.session.source),
+            ("offline", self.offline.value.to_string(), &self.offline.source),
+            ("user_agent", self.user_agent.value.clone(), &self.user_agent.source),
+            ("min_interval", min_interval, &self.min_interval.source),
+            ("profile", profile.0, profile.1),
+        ];
+        for (index, (name, value, source)) in rows.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:<12} {} ({})", name, value, source)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_layering() {
+        let dir = mock::TempDir::new("settings");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(
+            &config_path,
+            "cache_path = \"file-cache\"\nurl = \"http://file/2022\"\nsession = \"file-session\"\n",
+        )
+        .unwrap();
+        let session_path = dir.join("session");
+        fs::write(&session_path, "env-file-session\n").unwrap();
+
+        let vars = HashMap::from([
+            ("AOC_URL", "http://env/".to_string()),
+            ("AOC_SESSION_FILE", session_path.to_string_lossy().to_string()),
+        ]);
+        let env = |var: &str| vars.get(var).cloned();
+        let overrides = Overrides {
+            cache_path: Some(PathBuf::from("cli-cache")),
+            ..Default::default()
+        };
+        let settings = Settings::load_with(Some(&config_path), &overrides, &env).unwrap();
+
+        assert_eq!(settings.cache_path.value, PathBuf::from("cli-cache"));
+        assert_eq!(settings.cache_path.source, Source::CommandLine);
+        assert_eq!(settings.url.value.as_str(), "http://env/");
+        assert_eq!(settings.url.source, Source::Environment("AOC_URL"));
+        assert_eq!(settings.session.value, "env-file-session");
+        assert_eq!(
+            settings.session.source,
+            Source::SessionFile {
+                path: session_path,
+                via: Box::new(Source::Environment("AOC_SESSION_FILE"))
+            }
+        );
+        assert!(!settings.offline.value);
+        assert_eq!(settings.offline.source, Source::Default);
+
+        let settings = Settings::load_with(Some(&config_path), &Overrides::default(), &|_| None).unwrap();
+        assert_eq!(settings.url.value.as_str(), "http://file/");
+        assert_eq!(settings.session.source, Source::ConfigFile(config_path.clone()));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let dir = mock::TempDir::new("profiles");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(
+            &config_path,
+            "session = \"mine\"\n[profiles.alice]\nsession = \"alice-session\"\n[profiles.bob]\nsession = \"bob-session\"\ncache_dir = \"b\"\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load_with(Some(&config_path), &Overrides::default(), &|_| None).unwrap();
+        assert!(settings.profile.is_none());
+        assert_eq!(settings.session.value, "mine");
+        assert_eq!(settings.profiles, vec!["alice", "bob"]);
+        assert_eq!(settings.input_cache_path(), PathBuf::from(DEFAULT_CACHE_PATH));
+
+        let env = |var: &str| (var == "AOC_PROFILE").then(|| "bob".to_string());
+        let settings = Settings::load_with(Some(&config_path), &Overrides::default(), &env).unwrap();
+        assert_eq!(settings.session.value, "bob-session");
+        assert_eq!(settings.input_cache_path(), PathBuf::from(DEFAULT_CACHE_PATH).join("b"));
+
+        let overrides = Overrides {
+            profile: Some("alice".to_string()),
+            ..Default::default()
+        };
+        let settings = Settings::load_with(Some(&config_path), &overrides, &env).unwrap();
+        assert_eq!(settings.session.value, "alice-session");
+        assert_eq!(
+            settings.session.source,
+            Source::Profile {
+                name: "alice".to_string(),
+                path: config_path.clone()
+            }
+        );
+        assert_eq!(settings.input_cache_path(), PathBuf::from(DEFAULT_CACHE_PATH).join("alice"));
+
+        let overrides = Overrides {
+            profile: Some("carol".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Settings::load_with(Some(&config_path), &overrides, &|_| None),
+            Err(InputError::InvalidSetting { name: "profile", .. })
+        ));
+    }
+
+    #[test]
+    fn test_profile_session_wins() {
+        let dir = mock::TempDir::new("profile-session");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, "[profiles.alice]\nsession = \"a\"\n[profiles.carol]\n").unwrap();
+        let env = |var: &str| (var == "AOC_SESSION").then(|| "mine".to_string());
+        let overrides = |name: &str| Overrides {
+            profile: Some(name.to_string()),
+            session: Some("cli".to_string()),
+            ..Default::default()
+        };
+
+        let settings = Settings::load_with(Some(&config_path), &overrides("alice"), &env).unwrap();
+        assert_eq!(settings.session.value, "a");
+        assert_eq!(
+            settings.session.source,
+            Source::Profile {
+                name: "alice".to_string(),
+                path: config_path.clone()
+            }
+        );
+        // Without a session of its own a profile uses the usual layering
+        let settings = Settings::load_with(Some(&config_path), &overrides("carol"), &env).unwrap();
+        assert_eq!(settings.session.value, "cli");
+        let settings = Settings::load_with(Some(&config_path), &Overrides::default(), &env).unwrap();
+        assert_eq!(settings.session.value, "mine");
+    }
+
+    #[test]
+    fn test_profiles_without_top_level_session() {
+        let dir = mock::TempDir::new("profiles-only");
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml");
+        fs::write(&config_path, "[profiles.alice]\nsession = \"a\"\n[profiles.bob]\nsession = \"b\"\n").unwrap();
+
+        assert_eq!(Settings::profiles(Some(&config_path)).unwrap(), vec!["alice", "bob"]);
+        assert!(matches!(
+            Settings::load_with(Some(&config_path), &Overrides::default(), &|_| None),
+            Err(InputError::MissingSetting { name: "session" })
+        ));
+        for (name, session) in [("alice", "a"), ("bob", "b")] {
+            let overrides = Overrides {
+                profile: Some(name.to_string()),
+                ..Default::default()
+            };
+            let settings = Settings::load_with(Some(&config_path), &overrides, &|_| None).unwrap();
+            assert_eq!(settings.session.value, session);
+        }
+    }
+
+    #[test]
+    fn test_missing_session() {
+        let dir = mock::TempDir::new("settings-missing");
+        let missing = dir.join("config.toml");
+        assert!(Settings::load_with(Some(&missing), &Overrides::default(), &|_| None).is_err());
+        assert!(matches!(
+            Settings::load_with(None, &Overrides::default(), &|_| None),
+            Err(InputError::MissingSetting { name: "session" })
+        ));
+        let overrides = Overrides {
+            session: Some("cli".to_string()),
+            ..Default::default()
+        };
+        let settings = Settings::load_with(None, &overrides, &|_| None).unwrap();
+        assert_eq!(settings.cache_path.source, Source::Default);
+        assert_eq!(settings.url.value.as_str(), DEFAULT_URL);
+        assert_eq!(settings.min_interval.value, Duration::from_secs(5));
+
+        let env = |var: &str| (var == "AOC_MIN_INTERVAL").then(|| "soon".to_string());
+        assert!(matches!(
+            Settings::load_with(None, &overrides, &env),
+            Err(InputError::InvalidSetting { name: "AOC_MIN_INTERVAL", .. })
+        ));
+    }
+}
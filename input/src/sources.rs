@@ -0,0 +1,205 @@
+use crate::cache::{read_verified, unexpected_body, write_verified};
+use crate::{Client, InputError};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Somewhere puzzle inputs can be read from and optionally stored to.
+pub trait InputSource: Send + Sync {
+    /// The input for `day`, `None` when this source does not have it.
+    fn get(&self, year: u16, day: u8) -> Result<Option<String>, InputError>;
+
+    /// Describes where this source finds `day`, recorded in the metadata of cached inputs.
+    fn origin(&self, year: u16, day: u8) -> String;
+
+    /// Stores an input found at `origin`, sources that can't hold inputs ignore it.
+    fn put(&self, _year: u16, _day: u8, _input: &str, _origin: &str) -> Result<(), InputError> {
+        Ok(())
+    }
+}
+
+/// Inputs stored as `<path>/<year>/<day>/input`, the layout of the cache directory.
+pub struct DirectorySource {
+    path: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.path.join(year.to_string()).join(day.to_string()).join("input")
+    }
+}
+
+impl InputSource for DirectorySource {
+    fn get(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        read_verified(&self.input_path(year, day))
+    }
+
+    fn origin(&self, year: u16, day: u8) -> String {
+        self.input_path(year, day).to_string_lossy().to_string()
+    }
+
+    fn put(&self, year: u16, day: u8, input: &str, origin: &str) -> Result<(), InputError> {
+        write_verified(&self.input_path(year, day), input, origin)
+    }
+}
+
+/// Downloads inputs from the puzzle site.
+pub struct HttpSource {
+    client: Arc<Client>,
+}
+
+impl HttpSource {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+}
+
+impl InputSource for HttpSource {
+    fn get(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        let input = self.client.fetch_day(year, day, Some("input"))?;
+        match unexpected_body(&input) {
+            Some(reason) => Err(InputError::UnexpectedBody {
+                url: self.origin(year, day),
+                reason,
+            }),
+            None => Ok(Some(input)),
+        }
+    }
+
+    fn origin(&self, year: u16, day: u8) -> String {
+        self.client.day_url(year, day, Some("input")).to_string()
+    }
+}
+
+/// Reads standard input once and hands it out for whichever day asks.
+#[derive(Default)]
+pub struct StdinSource {
+    input: Mutex<Option<String>>,
+}
+
+impl StdinSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl InputSource for StdinSource {
+    fn get(&self, _year: u16, _day: u8) -> Result<Option<String>, InputError> {
+        let mut input = self.input.lock().unwrap_or_else(|e| e.into_inner());
+        if input.is_none() {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf).map_err(|e| InputError::ReadCache {
+                source: e,
+                path: "<stdin>".to_string(),
+            })?;
+            *input = Some(buf);
+        }
+        Ok(input.clone())
+    }
+
+    fn origin(&self, _year: u16, _day: u8) -> String {
+        "stdin".to_string()
+    }
+}
+
+/// Inputs held in memory, for tests and tools embedding the crate.
+#[derive(Default)]
+pub struct MemorySource {
+    inputs: Mutex<HashMap<(u16, u8), String>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(self, year: u16, day: u8, input: &str) -> Self {
+        self.inputs.lock().unwrap_or_else(|e| e.into_inner()).insert((year, day), input.to_string());
+        self
+    }
+}
+
+impl InputSource for MemorySource {
+    fn get(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        Ok(self.inputs.lock().unwrap_or_else(|e| e.into_inner()).get(&(year, day)).cloned())
+    }
+
+    fn origin(&self, _year: u16, _day: u8) -> String {
+        "memory".to_string()
+    }
+
+    fn put(&self, year: u16, day: u8, input: &str, _origin: &str) -> Result<(), InputError> {
+        self.inputs.lock().unwrap_or_else(|e| e.into_inner()).insert((year, day), input.to_string());
+        Ok(())
+    }
+}
+
+/// Tries each source in order, storing a hit in the sources before it so the next lookup stops earlier.
+pub struct ChainSource {
+    sources: Vec<Box<dyn InputSource>>,
+}
+
+impl ChainSource {
+    pub fn new(sources: Vec<Box<dyn InputSource>>) -> Self {
+        Self { sources }
+    }
+}
+
+impl InputSource for ChainSource {
+    fn get(&self, year: u16, day: u8) -> Result<Option<String>, InputError> {
+        for (i, source) in self.sources.iter().enumerate() {
+            if let Some(input) = source.get(year, day)? {
+                let origin = source.origin(year, day);
+                for earlier in &self.sources[..i] {
+                    earlier.put(year, day, &input, &origin)?;
+                }
+                return Ok(Some(input));
+            }
+        }
+        Ok(None)
+    }
+
+    fn origin(&self, year: u16, day: u8) -> String {
+        self.sources.iter().map(|source| source.origin(year, day)).collect::<Vec<_>>().join(", ")
+    }
+
+    fn put(&self, year: u16, day: u8, input: &str, origin: &str) -> Result<(), InputError> {
+        self.sources.iter().try_for_each(|source| source.put(year, day, input, origin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use url::Url;
+
+    #[test]
+    fn test_chain_fills_earlier_sources() {
+        let path = mock::TempDir::new("sources");
+        let memory = MemorySource::new().with(2022, 1, "1\n");
+        let chain = ChainSource::new(vec![Box::new(DirectorySource::new(&path)), Box::new(memory)]);
+        assert_eq!(chain.get(2022, 1).unwrap().as_deref(), Some("1\n"));
+        assert_eq!(chain.get(2022, 2).unwrap(), None);
+        assert_eq!(DirectorySource::new(&path).get(2022, 1).unwrap().as_deref(), Some("1\n"));
+    }
+
+    #[test]
+    fn test_input_with_memory_source() {
+        let path = mock::TempDir::new("memory");
+        let mut input = mock::input(&path, Url::parse("http://127.0.0.1:9/").unwrap());
+        input.set_source(Box::new(MemorySource::new().with(2022, 3, "abc\n")));
+        assert_eq!(input.get(2022, 3).unwrap(), "abc\n");
+        let error = input.get(2022, 4).unwrap_err();
+        assert!(matches!(error, InputError::NoInput { day: 4, .. }));
+        assert_eq!(error.to_string(), "no input for day 4 from memory");
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{Input, InputError};
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited { wait: Option<Duration> },
+    AlreadySolved,
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "correct"),
+            Submission::Incorrect => write!(f, "incorrect"),
+            Submission::TooHigh => write!(f, "incorrect, too high"),
+            Submission::TooLow => write!(f, "incorrect, too low"),
+            Submission::RateLimited { wait: Some(wait) } => write!(f, "rate limited, wait {:?}", wait),
+            Submission::RateLimited { wait: None } => write!(f, "rate limited"),
+            Submission::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+// "You have 1m 23s left to wait."
+fn parse_wait(body: &str) -> Option<Duration> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+    body[start..end].split_whitespace().try_fold(Duration::ZERO, |acc, part| {
+        let (value, unit) = part.split_at(part.len().checked_sub(1)?);
+        let value: u64 = value.parse().ok()?;
+        let secs = match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+        Some(acc + Duration::from_secs(secs))
+    })
+}
+
+pub(crate) fn parse_response(body: &str) -> Result<Submission, InputError> {
+    let submission = if body.contains("That's the right answer") {
+        Submission::Correct
+    } else if body.contains("That's not the right answer") {
+        if body.contains("your answer is too high") {
+            Submission::TooHigh
+        } else if body.contains("your answer is too low") {
+            Submission::TooLow
+        } else {
+            Submission::Incorrect
+        }
+    } else if body.contains("You gave an answer too recently") {
+        Submission::RateLimited { wait: parse_wait(body) }
+    } else if body.contains("You don't seem to be solving the right level") {
+        Submission::AlreadySolved
+    } else {
+        return Err(InputError::UnexpectedResponse);
+    };
+    Ok(submission)
+}
+
+impl Input {
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Submission, InputError> {
+        if self.client.offline {
+            return Err(InputError::Offline);
+        }
+        let new_url = self.client.day_url(year, day, Some("answer"));
+        let request = attohttpc::post(new_url.as_str())
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+            .map_err(InputError::Submit)?;
+        let response = self.client.send(request, InputError::Submit)?;
+        if response.is_success() {
+            parse_response(&response.text().map_err(InputError::Submit)?)
+        } else {
+            Err(InputError::GetFailed {
+                status: response.status().to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+
+    fn page(text: &str) -> String {
+        format!("<html><body><main><article><p>{}</p></article></main></body></html>", text)
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let correct = page("That's the right answer! You are one gold star closer to saving your vacation.");
+        assert_eq!(parse_response(&correct).unwrap(), Submission::Correct);
+        let high = page("That's not the right answer; your answer is too high. Please wait one minute before trying again.");
+        assert_eq!(parse_response(&high).unwrap(), Submission::TooHigh);
+        let low = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_response(&low).unwrap(), Submission::TooLow);
+        let wrong = page("That's not the right answer. If you're stuck, make sure you're using the full input data.");
+        assert_eq!(parse_response(&wrong).unwrap(), Submission::Incorrect);
+        let limited = page("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 23s left to wait.");
+        assert_eq!(
+            parse_response(&limited).unwrap(),
+            Submission::RateLimited {
+                wait: Some(Duration::from_secs(83))
+            }
+        );
+        let solved = page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_response(&solved).unwrap(), Submission::AlreadySolved);
+        assert!(matches!(parse_response(&page("Something else")), Err(InputError::UnexpectedResponse)));
+    }
+
+    #[test]
+    fn test_submit() {
+        let cache_path = mock::TempDir::new("submit");
+        let (url, server) = mock::serve(&[(200, &page("That's the right answer!"))]);
+        let input = mock::input(&cache_path, url);
+        assert_eq!(input.submit(2022, 6, 2, "1234").unwrap(), Submission::Correct);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/6/answer");
+        assert_eq!(requests[0].header("cookie"), Some("session=token"));
+        assert_eq!(requests[0].body, "level=2&answer=1234");
+    }
+}
@@ -0,0 +1,144 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Integer(i64),
+    Text(String),
+    /// Letters drawn across several lines, read off by eye (or OCR) before submitting
+    Multiline(String),
+    Unsolved,
+}
+
+impl Answer {
+    /// The text to send to the puzzle site, drawn answers have to be read off by hand.
+    pub fn submittable(&self) -> Option<String> {
+        match self {
+            Answer::Integer(value) => Some(value.to_string()),
+            Answer::Text(text) => Some(text.clone()),
+            Answer::Multiline(_) | Answer::Unsolved => None,
+        }
+    }
+
+    /// The answer on a single line, for tables and one-line output.
+    pub fn inline(&self) -> String {
+        match self {
+            Answer::Multiline(text) => text.lines().collect::<Vec<_>>().join(" / "),
+            answer => answer.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(text) | Answer::Multiline(text) => write!(f, "{}", text),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($int:ty),+) => {
+        $(
+        impl From<$int> for Answer {
+            /// Values that don't fit an `i64` are kept exact as text rather than wrapping around.
+            fn from(value: $int) -> Self {
+                i64::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Integer)
+            }
+        }
+        )+
+    };
+}
+
+integer_answer!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        if text.contains('\n') {
+            Answer::Multiline(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        text.to_string().into()
+    }
+}
+
+/// `None` for a part that has no answer yet.
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+/// Extra information a part reports alongside its answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub label: &'static str,
+    pub value: String,
+}
+
+/// What a part of a `Day` returns, its answer and any diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    pub answer: Answer,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Output {
+    pub fn with(mut self, label: &'static str, value: impl fmt::Display) -> Self {
+        self.diagnostics.push(Diagnostic {
+            label,
+            value: value.to_string(),
+        });
+        self
+    }
+}
+
+impl<T: Into<Answer>> From<T> for Output {
+    fn from(answer: T) -> Self {
+        Self {
+            answer: answer.into(),
+            diagnostics: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(24000usize), Answer::Integer(24000));
+        assert_eq!(Answer::from(u64::MAX), Answer::Text("18446744073709551615".to_string()));
+        assert_eq!(Answer::from("CMZ"), Answer::Text("CMZ".to_string()));
+        let drawn = Answer::from("#..#\n####\n");
+        assert!(matches!(drawn, Answer::Multiline(_)));
+        assert_eq!(drawn.submittable(), None);
+        assert_eq!(drawn.inline(), "#..# / ####");
+        assert_eq!(Answer::Integer(-3).submittable().as_deref(), Some("-3"));
+        assert_eq!(Answer::from(None::<i32>), Answer::Unsolved);
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+
+        let json = serde_json::to_string(&[Answer::Integer(7), Answer::Text("CMZ".to_string()), Answer::Unsolved]).unwrap();
+        assert_eq!(json, r#"[7,"CMZ",null]"#);
+
+        let output = Output::from(5).with("elves", 3);
+        assert_eq!(
+            output.diagnostics,
+            vec![Diagnostic {
+                label: "elves",
+                value: "3".to_string()
+            }]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use crate::solver::{Solver, Timings};
+use color_eyre::Result;
+use std::time::Duration;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let len = sorted.len();
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+        // Nearest rank percentile
+        let p95 = sorted[((len * 95).div_ceil(100)).max(1) - 1];
+        let mean = sorted.iter().sum::<Duration>() / len as u32;
+        let variance = sorted.iter().fold(0.0, |acc, sample| acc + (sample.as_secs_f64() - mean.as_secs_f64()).powi(2)) / len as f64;
+        Self {
+            min: sorted[0],
+            median,
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub fn bench(solver: &dyn Solver, input: &str, part: Option<u8>, runs: usize, warmup: usize) -> Result<Vec<Timings>> {
+    for _ in 0..warmup {
+        solver.solve(input, part)?;
+    }
+    (0..runs).map(|_| Ok(solver.solve(input, part)?.1)).collect()
+}
+
+pub fn report(day: usize, samples: &[Timings]) {
+    let phase = |f: fn(&Timings) -> Duration| Stats::from_samples(&samples.iter().map(f).collect::<Vec<_>>());
+    println!("Day {} ({} runs)", day, samples.len());
+    println!("  {:<8}{:>12}{:>12}{:>12}{:>12}{:>12}", "", "min", "median", "mean", "p95", "stddev");
+    for (name, stats) in [
+        ("Parse", phase(|t| t.parse)),
+        ("Part 1", phase(|t| t.part1)),
+        ("Part 2", phase(|t| t.part2)),
+        ("Total", phase(|t| t.total())),
+    ] {
+        println!(
+            "  {:<8}{:>12}{:>12}{:>12}{:>12}{:>12}",
+            name,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.p95),
+            format!("{:.2?}", stats.stddev)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+
+        let stats = Stats::from_samples(&[4, 1, 3, 2].map(Duration::from_millis));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+    }
+}
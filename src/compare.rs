@@ -0,0 +1,24 @@
+use crate::summary::{failure, table, DayRun};
+
+/// Prints each day's answers with one column per profile, so an answer that only fails on some inputs stands out.
+pub fn print(profiles: &[(String, Vec<DayRun>)]) {
+    let mut header = vec!["Day".to_string(), "Part".to_string()];
+    header.extend(profiles.iter().map(|(name, _)| name.clone()));
+    let days = profiles
+        .first()
+        .map(|(_, runs)| runs.iter().map(|run| run.day).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let mut rows = Vec::new();
+    for (index, day) in days.iter().enumerate() {
+        for part in [1, 2] {
+            let mut cells = vec![day.to_string(), part.to_string()];
+            cells.extend(profiles.iter().map(|(_, runs)| match &runs[index].result {
+                Ok((solution, _)) => solution.labelled(part),
+                Err(e) => failure(e),
+            }));
+            rows.push(cells);
+        }
+    }
+
+    table(&header, &rows);
+}
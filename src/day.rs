@@ -1,6 +1,21 @@
 use super::*;
+use crate::solver::NotImplemented;
 use color_eyre::Result;
 
-pub fn solve(input: &str) -> Result<Solution> {
-    todo!()
+pub struct DayN;
+
+impl Day for DayN {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Output> {
+        Err(NotImplemented.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Output> {
+        Err(NotImplemented.into())
+    }
 }
@@ -0,0 +1,121 @@
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Once};
+use std::thread;
+use std::time::Duration;
+
+/// Why a day stopped without producing a result.
+#[derive(Debug)]
+pub enum Abort {
+    Panic { message: String, location: Option<String> },
+    Timeout(Duration),
+}
+
+impl Abort {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Abort::Panic { .. } => "panicked",
+            Abort::Timeout(_) => "timed out",
+        }
+    }
+}
+
+impl fmt::Display for Abort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Abort::Panic {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {}: {}", location, message),
+            Abort::Panic { message, location: None } => write!(f, "panicked: {}", message),
+            Abort::Timeout(timeout) => write!(f, "timed out after {:.2?}", timeout),
+        }
+    }
+}
+
+impl std::error::Error for Abort {}
+
+thread_local! {
+    static GUARDED: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// Keeps panics in guarded threads quiet, recording where they happened for the report instead.
+fn install_hook() {
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if GUARDED.with(Cell::get) {
+                LOCATION.with(|location| *location.borrow_mut() = info.location().map(|location| location.to_string()));
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| "unknown panic payload".to_string()),
+    }
+}
+
+/// Runs `f` on its own thread, turning a panic or running past `timeout` into an `Abort` error. A thread that
+/// times out is left behind, it ends with the process.
+pub fn run<R, F>(name: String, timeout: Option<Duration>, f: F) -> Result<R>
+where
+    R: Send + 'static,
+    F: FnOnce() -> Result<R> + Send + 'static,
+{
+    install_hook();
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new().name(name).spawn(move || {
+        GUARDED.with(|guarded| guarded.set(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+            Err(Abort::Panic {
+                message: panic_message(payload.as_ref()),
+                location: LOCATION.with(|location| location.borrow_mut().take()),
+            }
+            .into())
+        });
+        // The receiver is gone once the run timed out
+        let _ = sender.send(result);
+    })?;
+    match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).unwrap_or_else(|_| Err(Abort::Timeout(timeout).into())),
+        None => receiver.recv().unwrap_or_else(|_| Err(eyre!("thread exited without a result"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run("ok".to_string(), None, || Ok(1)).unwrap(), 1);
+
+        let error = run::<(), _>("panic".to_string(), None, || panic!("boom {}", 1)).unwrap_err();
+        match error.downcast_ref::<Abort>() {
+            Some(Abort::Panic { message, location }) => {
+                assert_eq!(message, "boom 1");
+                assert!(location.as_deref().unwrap().starts_with("src/guard.rs:"));
+            }
+            other => panic!("expected a panic, got {:?}", other),
+        }
+
+        let error = run::<(), _>("hang".to_string(), Some(Duration::from_millis(50)), || {
+            thread::sleep(Duration::from_secs(5));
+            Ok(())
+        })
+        .unwrap_err();
+        assert!(matches!(error.downcast_ref::<Abort>(), Some(Abort::Timeout(_))));
+    }
+}
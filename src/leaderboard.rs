@@ -0,0 +1,65 @@
+use crate::summary::table;
+use input::{unlocks_at, Leaderboard, Member};
+use std::time::Duration;
+
+/// Time taken since the puzzle unlocked, in the `HH:MM:SS` form the puzzle site uses.
+fn elapsed(time: Duration) -> String {
+    let secs = time.as_secs();
+    if secs >= 24 * 3600 {
+        return ">24h".to_string();
+    }
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+pub fn print(year: u16, id: u64, leaderboard: &Leaderboard) {
+    let standings = leaderboard.standings();
+    println!("Private leaderboard {} for {}", id, year);
+    println!();
+    let header = ["Rank", "Score", "Stars", "Name"].map(String::from);
+    let rows = standings
+        .iter()
+        .enumerate()
+        .map(|(rank, member)| {
+            vec![
+                format!("{})", rank + 1),
+                member.local_score.to_string(),
+                member.stars.to_string(),
+                member.display_name(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table(&header, &rows);
+
+    for day in 1..=25 {
+        let unlocked = unlocks_at(year, day);
+        let time = |member: &Member, part| member.star(day, part).map(|star| star.duration_since(unlocked).unwrap_or_default());
+        let mut finished = standings
+            .iter()
+            .filter_map(|member| Some((member, time(member, 1)?, time(member, 2))))
+            .collect::<Vec<_>>();
+        if finished.is_empty() {
+            continue;
+        }
+        // Both stars first, then by how quickly the last star came
+        finished.sort_by_key(|(_, part1, part2)| (part2.is_none(), part2.unwrap_or(*part1)));
+        println!();
+        println!("Day {}", day);
+        let header = ["Name", "Part 1", "Part 2"].map(String::from);
+        let rows = finished
+            .iter()
+            .map(|(member, part1, part2)| vec![member.display_name(), elapsed(*part1), part2.map_or("-".to_string(), elapsed)])
+            .collect::<Vec<_>>();
+        table(&header, &rows);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elapsed() {
+        assert_eq!(elapsed(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(elapsed(Duration::from_secs(24 * 3600)), ">24h");
+    }
+}
@@ -1,61 +1,632 @@
-use clap::Parser;
-use color_eyre::eyre::bail;
+use answer::Output;
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{bail, eyre};
 use color_eyre::Result;
-use input::Input;
-use std::path::PathBuf;
-use std::time::Instant;
+use input::{format_utc, unlocks_at, CacheStatus, Input, InputError, Overrides, Prefetch, Settings, Submission};
+use output::Format;
+use solver::{Day, Solution, Solver, Status, Timings};
+use std::fs;
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use summary::DayRun;
 
-days! {day1, day2, day3, day4, day5, day6, day7, day8}
+mod answer;
+mod bench;
+mod compare;
+mod guard;
+mod leaderboard;
+mod output;
+mod parse;
+mod pool;
+mod solver;
+mod summary;
+
+years! {year2022 => 2022}
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Config path
-    #[arg(short, long, default_value = "config.toml")]
-    config: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Config path (defaults to config.toml if present)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Session token, overrides AOC_SESSION and the config file but not a profile's own session
+    #[arg(long)]
+    session: Option<String>,
+    /// File containing the session token
+    #[arg(long, conflicts_with = "session")]
+    session_file: Option<PathBuf>,
+    /// Input cache directory
+    #[arg(long)]
+    cache_path: Option<PathBuf>,
+    /// Base url of the puzzle site
+    #[arg(long)]
+    url: Option<String>,
+    /// Only use cached data, never touch the network
+    #[arg(long)]
+    offline: bool,
+    /// Account profile from the config file
+    #[arg(short, long)]
+    profile: Option<String>,
+    /// Solve against every profile's input and show the answers side by side
+    #[arg(long, conflicts_with_all = ["profile", "session", "session_file", "bench", "submit", "verify", "example", "format"])]
+    all_profiles: bool,
+    /// Event year (defaults to latest)
+    #[arg(short, long)]
+    year: Option<u16>,
     /// Days to run (defaults to last)
+    #[arg(conflicts_with = "all")]
     days: Option<Vec<u32>>,
+    /// Run every registered day and print a summary table
+    #[arg(long)]
+    all: bool,
+    /// Run each day this many times and report timing statistics
+    #[arg(long, value_name = "N")]
+    bench: Option<usize>,
+    /// Solve the day and submit the answer for this part
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2), conflicts_with_all = ["all", "bench"])]
+    submit: Option<u8>,
+    /// Only run this part of each day
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2), conflicts_with = "submit")]
+    part: Option<u8>,
+    /// Check answers against the ones recorded when they were accepted or shown on a solved puzzle page
+    #[arg(long, conflicts_with_all = ["bench", "submit"])]
+    verify: bool,
+    /// Read the input from this file, or stdin for -, instead of the cache (needs no config file)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["all", "all_profiles", "submit", "verify", "example", "wait"])]
+    input: Option<PathBuf>,
+    /// Run against the k-th example from the puzzle page instead of the real input
+    #[arg(long, value_name = "K", num_args = 0..=1, default_missing_value = "1", conflicts_with_all = ["submit", "verify"])]
+    example: Option<usize>,
+    /// Output format for results
+    #[arg(long, value_enum, default_value_t = Format::Text, conflicts_with_all = ["bench", "submit", "verify"])]
+    format: Format,
+    /// If the day has not unlocked yet, count down and fetch its input as soon as it does
+    #[arg(long, conflicts_with_all = ["offline", "all_profiles"])]
+    wait: bool,
+    /// Number of days to run concurrently, reports wall clock against the summed CPU time of each day
+    #[arg(short, long, default_value_t = 1, conflicts_with_all = ["bench", "submit", "verify"])]
+    jobs: usize,
+    /// Seconds each day may run before it is reported as timed out, 0 to wait forever. Benchmarks get this
+    /// once per run
+    #[arg(long, value_name = "SECS", default_value_t = 60.0)]
+    timeout: f64,
+    /// Untimed runs before benchmarking
+    #[arg(long, default_value_t = 3)]
+    warmup: usize,
 }
-pub struct Solution {
-    pub first: String,
-    pub second: String,
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the puzzle description for a day
+    Describe { day: u8 },
+    /// Show the resolved configuration and where each value came from
+    Config,
+    /// Show standings and star times for a private leaderboard
+    Leaderboard { id: u64 },
+    /// Manage the session token
+    Session {
+        #[command(subcommand)]
+        command: SessionCommand,
+    },
+    /// Inspect and maintain the input cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
 }
 
-fn run_method(input: &Input, day: usize, days: &[fn(&str) -> Result<Solution>]) -> Result<()> {
-    if let Some(method) = days.get(day - 1) {
-        let input = input.get(day as u8)?;
-        let now = Instant::now();
-        let result = method(input.as_str())?;
-        let total = now.elapsed();
-        println!("Day {} {:?} Solution - {} {}", day, total, result.first, result.second);
-        Ok(())
+#[derive(Debug, Subcommand)]
+enum SessionCommand {
+    /// Check the session token is still accepted by the puzzle site
+    Check,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// List cached days with their size and fetch time
+    List,
+    /// Download every unlocked input in a range of days, such as 5 or 1-25
+    Prefetch {
+        #[arg(value_parser = parse_day_range)]
+        days: RangeInclusive<u8>,
+    },
+    /// Remove fetched data for a day, or all days, keeping recorded answers
+    Purge {
+        #[arg(value_name = "DAY|all", value_parser = parse_purge)]
+        day: Purge,
+    },
+    /// Check cached inputs against their recorded length and hash
+    Verify,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Purge {
+    All,
+    Day(u8),
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    match day.trim().parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("{:?} is not a day between 1 and 25", day)),
+    }
+}
+
+fn parse_day_range(range: &str) -> Result<RangeInclusive<u8>, String> {
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+        None => (parse_day(range)?, parse_day(range)?),
+    };
+    if start > end {
+        return Err(format!("{} is an empty range", range));
+    }
+    Ok(start..=end)
+}
+
+fn parse_purge(day: &str) -> Result<Purge, String> {
+    match day {
+        "all" => Ok(Purge::All),
+        day => parse_day(day).map(Purge::Day),
+    }
+}
+
+fn read_input(path: &Path) -> Result<String> {
+    let mut text = String::new();
+    if path == Path::new("-") {
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| eyre!("Failed to read input from stdin: {}", e))?;
     } else {
-        bail!("Invalid day {}", day);
+        text = fs::read_to_string(path).map_err(|e| eyre!("Failed to read input {}: {}", path.display(), e))?;
+    }
+    Ok(text)
+}
+
+fn countdown(left: Duration) -> String {
+    let secs = left.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+/// Counts down on stderr until `day` unlocks, then downloads its input, retrying briefly in case the local clock
+/// runs ahead of the puzzle site.
+fn wait_for_unlock(input: &Input, year: u16, day: u8) -> Result<()> {
+    let unlocks_at = unlocks_at(year, day);
+    if SystemTime::now() >= unlocks_at {
+        return Ok(());
+    }
+    while let Ok(left) = unlocks_at.duration_since(SystemTime::now()) {
+        eprint!("\rDay {} unlocks in {}  ", day, countdown(left + Duration::from_millis(999)));
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+    eprintln!("\rDay {} unlocked          ", day);
+    for attempt in 1.. {
+        match input.get(year, day) {
+            Err(InputError::GetFailed { .. } | InputError::NotYetUnlocked { .. }) if attempt < 5 => thread::sleep(Duration::from_secs(2)),
+            result => {
+                result?;
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the session once up front when any of the `registered` days in `days` still has to be downloaded, so
+/// an expired token is reported as such instead of as a failed download for every day.
+fn check_session_for(input: &Input, settings: &Settings, year: u16, days: &[usize], registered: usize) -> Result<()> {
+    if settings.offline.value {
+        return Ok(());
+    }
+    let cached = input.cached(year)?.into_iter().map(|entry| entry.day as usize).collect::<Vec<_>>();
+    if days.iter().any(|day| (1..=registered).contains(day) && !cached.contains(day)) {
+        input.check_session()?;
+    }
+    Ok(())
+}
+
+fn cache_command(input: &Input, year: u16, command: CacheCommand) -> Result<()> {
+    match command {
+        CacheCommand::List => {
+            for entry in input.cached(year)? {
+                let fetched = entry.metadata.as_ref().map_or("unknown".to_string(), |meta| format_utc(meta.fetched));
+                println!("Day {:>2} {:>8} bytes  fetched {}", entry.day, entry.size, fetched);
+            }
+        }
+        CacheCommand::Prefetch { days } => {
+            for day in days {
+                match input.prefetch(year, day)? {
+                    Prefetch::Cached => println!("Day {:>2} already cached", day),
+                    Prefetch::Downloaded => println!("Day {:>2} downloaded", day),
+                    Prefetch::Locked => {
+                        println!("Day {:>2} not unlocked yet", day);
+                        break;
+                    }
+                }
+            }
+        }
+        CacheCommand::Purge { day } => {
+            let purged = match day {
+                Purge::All => input.purge(year, None)?,
+                Purge::Day(day) => input.purge(year, Some(day))?,
+            };
+            println!("Purged {} day(s)", purged);
+        }
+        CacheCommand::Verify => {
+            let mut corrupt = 0;
+            for (day, status) in input.verify_cache(year)? {
+                match status {
+                    CacheStatus::Ok => println!("Day {:>2} ok", day),
+                    CacheStatus::Unverified => println!("Day {:>2} no metadata", day),
+                    CacheStatus::Corrupt => {
+                        corrupt += 1;
+                        println!("Day {:>2} CORRUPT", day);
+                    }
+                }
+            }
+            if corrupt > 0 {
+                bail!("{} cached input(s) do not match their metadata, purge them to refetch", corrupt);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where a runner reads each day's input from.
+enum DayInput<'a> {
+    Cache(&'a Input),
+    /// Given with --input, every selected day runs against it
+    Text(String),
+}
+
+struct Runner<'a> {
+    input: DayInput<'a>,
+    year: u16,
+    days: Vec<&'static dyn Solver>,
+    example: Option<usize>,
+    /// Only run this part
+    part: Option<u8>,
+    timeout: Option<Duration>,
+}
+
+impl Runner<'_> {
+    fn get_solver(&self, day: usize) -> Result<&'static dyn Solver> {
+        match self.days.get(day.wrapping_sub(1)) {
+            Some(solver) => Ok(*solver),
+            None => bail!("Invalid day {}", day),
+        }
+    }
+
+    fn cache(&self) -> Result<&Input> {
+        match self.input {
+            DayInput::Cache(input) => Ok(input),
+            DayInput::Text(_) => bail!("Not available with --input"),
+        }
+    }
+
+    fn get_input(&self, day: usize) -> Result<String> {
+        if let DayInput::Text(text) = &self.input {
+            return Ok(text.clone());
+        }
+        match self.example {
+            Some(example) => self
+                .cache()?
+                .examples(self.year, day as u8)?
+                .into_iter()
+                .nth(example.wrapping_sub(1))
+                .ok_or_else(|| eyre!("Day {} has no example {}", day, example)),
+            None => Ok(self.cache()?.get(self.year, day as u8)?),
+        }
+    }
+
+    fn solve_day(&self, day: usize) -> Result<(Solution, Timings)> {
+        self.solve_parts(day, self.part)
+    }
+
+    fn solve_parts(&self, day: usize, part: Option<u8>) -> Result<(Solution, Timings)> {
+        let solver = self.get_solver(day)?;
+        let input = self.get_input(day)?;
+        guard::run(format!("day {}", day), self.timeout, move || solver.solve(input.as_str(), part))
+    }
+
+    fn bench_method(&self, day: usize, runs: usize, warmup: usize) -> Result<()> {
+        let solver = self.get_solver(day)?;
+        let input = self.get_input(day)?;
+        let part = self.part;
+        // The timeout is per solve, so a benchmark gets it once for every run including the warmup
+        let timeout = self.timeout.and_then(|timeout| timeout.checked_mul(u32::try_from(runs + warmup).ok()?));
+        let samples = guard::run(format!("day {}", day), timeout, move || {
+            bench::bench(solver, input.as_str(), part, runs, warmup)
+        })?;
+        bench::report(day, &samples);
+        Ok(())
+    }
+
+    fn submit_method(&self, day: usize, part: u8) -> Result<()> {
+        let (result, _) = self.solve_parts(day, Some(part))?;
+        let answer = match result.answer(part) {
+            Some(answer) => answer
+                .submittable()
+                .ok_or_else(|| eyre!("Day {} Part {} has no answer that can be submitted: {}", day, part, answer))?,
+            None => bail!("Day {} Part {} is {}", day, part, result.status(part).name()),
+        };
+        println!("Day {} Part {} submitting {}", day, part, answer);
+        let submission = self.cache()?.submit(self.year, day as u8, part, &answer)?;
+        println!("  {}", submission);
+        match submission {
+            Submission::Correct => self.cache()?.record_answer(self.year, day as u8, part, &answer)?,
+            // The site doesn't check resubmissions, the accepted answer is read off the puzzle page instead
+            Submission::AlreadySolved => match self.cache()?.record_solved_answer(self.year, day as u8, part)? {
+                Some(accepted) if accepted == answer => println!("  Recorded accepted answer {}", accepted),
+                Some(accepted) => println!("  Recorded accepted answer {}, which differs from {}", accepted, answer),
+                None => println!("  No accepted answer found on the puzzle page"),
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Compares the day's answers against the recorded ones, returning how many parts were compared and whether
+    /// they all matched.
+    fn verify_method(&self, day: usize) -> Result<(usize, bool)> {
+        let (result, _) = self.solve_day(day)?;
+        let mut compared = 0;
+        let mut matched = true;
+        for part in [1, 2] {
+            let actual = match result.status(part) {
+                Status::Solved(output) => output.answer.to_string(),
+                Status::NotImplemented => {
+                    println!("Day {} Part {} not implemented", day, part);
+                    continue;
+                }
+                Status::Skipped => continue,
+            };
+            let expected = self.cache()?.answer(self.year, day as u8, part)?;
+            if expected.is_some() {
+                compared += 1;
+            }
+            match expected {
+                Some(expected) if expected == actual => println!("Day {} Part {} ok", day, part),
+                Some(expected) => {
+                    matched = false;
+                    println!("Day {} Part {} MISMATCH", day, part);
+                    println!("  - expected {}", expected);
+                    println!("  + actual   {}", actual);
+                }
+                None => println!("Day {} Part {} no recorded answer", day, part),
+            }
+        }
+        Ok((compared, matched))
     }
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let input = Input::open(args.config)?;
-    let day_methods = days();
-    if let Some(days) = args.days {
-        for day in days {
-            run_method(&input, day as usize, &day_methods)?;
+    let overrides = Overrides {
+        cache_path: args.cache_path,
+        url: args.url,
+        session: args.session,
+        session_file: args.session_file,
+        offline: args.offline.then_some(true),
+        profile: args.profile,
+    };
+    // A given input needs no settings, so a missing session or config file doesn't stop it
+    let (text, cache) = match &args.input {
+        Some(path) => (Some(read_input(path)?), None),
+        // Every profile loads its own settings, the top level of the config may have no session
+        None if args.all_profiles && args.command.is_none() => (None, None),
+        None => {
+            let settings = Settings::load(args.config.as_deref(), &overrides)?;
+            let input = Input::new(&settings);
+            (None, Some((settings, input)))
         }
-    } else {
-        run_method(&input, day_methods.len(), &day_methods)?;
+    };
+    let mut years = years();
+    let (year, days) = match args.year {
+        Some(year) => match years.into_iter().find(|(registered, _)| *registered == year) {
+            Some(registered) => registered,
+            None => bail!("No days registered for {}", year),
+        },
+        None => years.pop().expect("At least one year registered"),
+    };
+    if let Some(command) = args.command {
+        let Some((settings, input)) = &cache else {
+            bail!("Subcommands can't be used with --input")
+        };
+        return match command {
+            Command::Describe { day } => {
+                println!("{}", input.puzzle(year, day)?);
+                Ok(())
+            }
+            Command::Config => {
+                println!("{}", settings);
+                Ok(())
+            }
+            Command::Leaderboard { id } => {
+                leaderboard::print(year, id, &input.leaderboard(year, id)?);
+                Ok(())
+            }
+            Command::Session {
+                command: SessionCommand::Check,
+            } => {
+                input.check_session()?;
+                println!("Session ok");
+                Ok(())
+            }
+            Command::Cache { command } => cache_command(input, year, command),
+        };
+    }
+    let timeout = match Duration::try_from_secs_f64(args.timeout) {
+        Ok(timeout) if timeout.is_zero() => None,
+        Ok(timeout) => Some(timeout),
+        Err(_) => bail!("Invalid timeout {}", args.timeout),
+    };
+    let selected = match args.days {
+        Some(selected) => selected.into_iter().map(|day| day as usize).collect(),
+        None if args.all || args.verify => (1..=days.len()).collect(),
+        None => vec![days.len()],
+    };
+    if args.all_profiles {
+        let names = Settings::profiles(args.config.as_deref())?;
+        if names.is_empty() {
+            bail!("No profiles defined in the config file");
+        }
+        let mut profiles = Vec::new();
+        for name in &names {
+            let overrides = Overrides {
+                profile: Some(name.clone()),
+                ..overrides.clone()
+            };
+            let settings = Settings::load(args.config.as_deref(), &overrides)?;
+            let input = Input::new(&settings);
+            check_session_for(&input, &settings, year, &selected, days.len())?;
+            let runner = Runner {
+                input: DayInput::Cache(&input),
+                year,
+                days: days.clone(),
+                example: None,
+                part: args.part,
+                timeout,
+            };
+            let runs = pool::map(selected.clone(), args.jobs, |day| DayRun {
+                year,
+                day,
+                result: runner.solve_day(day),
+            });
+            profiles.push((name.clone(), runs));
+        }
+        compare::print(&profiles);
+        let failed = profiles.iter().flat_map(|(_, runs)| runs).filter(|run| run.failed()).count();
+        if failed > 0 {
+            bail!("{} run(s) failed", failed);
+        }
+        return Ok(());
+    }
+    let input = match (text, &cache) {
+        (Some(text), _) => DayInput::Text(text),
+        (None, Some((settings, input))) => {
+            if args.example.is_none() {
+                check_session_for(input, settings, year, &selected, days.len())?;
+            }
+            if args.wait {
+                if let Some(last) = selected.iter().filter(|day| (1..=25).contains(*day)).max() {
+                    wait_for_unlock(input, year, *last as u8)?;
+                }
+            }
+            DayInput::Cache(input)
+        }
+        (None, None) => unreachable!("settings are loaded without --input"),
+    };
+    let runner = Runner {
+        input,
+        year,
+        days,
+        example: args.example,
+        part: args.part,
+        timeout,
+    };
+    if args.verify {
+        let mut failed = 0;
+        let mut compared = 0;
+        for day in selected {
+            match runner.verify_method(day) {
+                Ok((parts, matched)) => {
+                    compared += parts;
+                    if !matched {
+                        failed += 1;
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("Day {} FAILED: {:#}", day, e);
+                }
+            }
+        }
+        if failed > 0 {
+            bail!("{} day(s) failed verification", failed);
+        }
+        if compared == 0 {
+            bail!("No recorded answers to verify against, submit or describe a solved day to record them");
+        }
+        return Ok(());
+    }
+    if let Some(part) = args.submit {
+        if selected.len() != 1 {
+            bail!("--submit requires a single day");
+        }
+        return runner.submit_method(selected[0], part);
+    }
+    if let Some(runs) = args.bench {
+        let mut failed = 0;
+        for day in selected {
+            if let Err(e) = runner.bench_method(day, runs, args.warmup) {
+                failed += 1;
+                println!("Day {} FAILED: {:#}", day, e);
+            }
+        }
+        if failed > 0 {
+            bail!("{} day(s) failed", failed);
+        }
+        return Ok(());
+    }
+    let now = Instant::now();
+    let runs = pool::map(selected, args.jobs, |day| DayRun {
+        year,
+        day,
+        result: runner.solve_day(day),
+    });
+    let wall = now.elapsed();
+    output::print(args.format, &runs, args.all)?;
+    if args.jobs > 1 {
+        let cpu = runs
+            .iter()
+            .filter_map(|run| run.result.as_ref().ok())
+            .map(|(_, timings)| timings.cpu)
+            .sum::<Option<Duration>>();
+        match cpu {
+            Some(cpu) => eprintln!(
+                "Wall clock {:.2?}, summed CPU {:.2?} ({:.2}x) on {} jobs",
+                wall,
+                cpu,
+                cpu.as_secs_f64() / wall.as_secs_f64(),
+                args.jobs
+            ),
+            None => eprintln!("Wall clock {:.2?} on {} jobs", wall, args.jobs),
+        }
+    }
+    let failed = runs.iter().filter(|run| run.failed()).count();
+    if failed > 0 {
+        bail!("{} day(s) failed", failed);
     }
     Ok(())
 }
 
 #[macro_export]
 macro_rules! days {
-    ($($day:ident),+) => {
+    ($($day:ident::$solver:ident),+) => {
         $(
         mod $day;
         )+
 
-        fn days() -> Vec<fn(&str) ->  Result<Solution>> {
-            vec![$($day::solve,)+]
+        pub fn days() -> Vec<&'static dyn Solver> {
+            vec![$(&$day::$solver,)+]
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! years {
+    ($($module:ident => $year:literal),+) => {
+        $(
+        mod $module;
+        )+
+
+        /// Registered years in ascending order, each with its days.
+        fn years() -> Vec<(u16, Vec<&'static dyn Solver>)> {
+            vec![$(($year, $module::days()),)+]
         }
     };
 }
@@ -0,0 +1,159 @@
+use crate::answer::{Answer, Diagnostic};
+use crate::guard::Abort;
+use crate::solver::Status;
+use crate::summary::{self, DayRun};
+use clap::ValueEnum;
+use color_eyre::Result;
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// One row of machine readable output, a single part of a single day.
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    year: u16,
+    day: usize,
+    part: u8,
+    status: &'static str,
+    answer: Option<&'a Answer>,
+    label: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    diagnostics: &'a [Diagnostic],
+    parse_ns: Option<u64>,
+    part_ns: Option<u64>,
+    error: Option<String>,
+}
+
+fn records(runs: &[DayRun]) -> Vec<Record<'_>> {
+    runs.iter()
+        .flat_map(|run| {
+            [1, 2].map(|part| match &run.result {
+                Ok((solution, timings)) => Record {
+                    year: run.year,
+                    day: run.day,
+                    part,
+                    status: solution.status(part).name(),
+                    answer: solution.answer(part),
+                    label: solution.label(part),
+                    diagnostics: solution.diagnostics(part),
+                    parse_ns: Some(timings.parse.as_nanos() as u64),
+                    part_ns: Some(timings.part(part).as_nanos() as u64),
+                    error: None,
+                },
+                Err(e) => Record {
+                    year: run.year,
+                    day: run.day,
+                    part,
+                    status: e.downcast_ref::<Abort>().map_or("failed", Abort::kind),
+                    answer: None,
+                    label: None,
+                    diagnostics: &[],
+                    parse_ns: None,
+                    part_ns: None,
+                    error: Some(format!("{:#}", e)),
+                },
+            })
+        })
+        .collect()
+}
+
+fn cells(record: &Record) -> [String; 9] {
+    let optional = |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+    [
+        record.year.to_string(),
+        record.day.to_string(),
+        record.part.to_string(),
+        record.status.to_string(),
+        record.answer.map(Answer::to_string).unwrap_or_default(),
+        record.label.unwrap_or_default().to_string(),
+        optional(record.parse_ns),
+        optional(record.part_ns),
+        record.error.clone().unwrap_or_default(),
+    ]
+}
+
+const HEADER: [&str; 9] = ["year", "day", "part", "status", "answer", "label", "parse_ns", "part_ns", "error"];
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn markdown_escape(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn print_text(runs: &[DayRun]) {
+    for run in runs {
+        match &run.result {
+            Ok((solution, timings)) => {
+                println!("Day {} {:?}", run.day, timings.total());
+                println!("  Parse  {:?}", timings.parse);
+                for part in [1, 2] {
+                    match solution.status(part) {
+                        Status::Skipped => continue,
+                        Status::NotImplemented => println!("  Part {} - not implemented", part),
+                        Status::Solved(output) => match &output.answer {
+                            Answer::Multiline(text) => {
+                                println!("  Part {} {:?} - {}", part, timings.part(part), solution.label(part).unwrap_or_default());
+                                for line in text.lines() {
+                                    println!("    {}", line);
+                                }
+                            }
+                            _ => println!("  Part {} {:?} - {}", part, timings.part(part), solution.labelled(part)),
+                        },
+                    }
+                    for diagnostic in solution.diagnostics(part) {
+                        println!("    {}: {}", diagnostic.label, diagnostic.value);
+                    }
+                }
+            }
+            Err(e) => println!("Day {} FAILED: {:#}", run.day, e),
+        }
+    }
+}
+
+pub fn print(format: Format, runs: &[DayRun], table: bool) -> Result<()> {
+    match format {
+        Format::Text if table => summary::print(runs),
+        Format::Text => print_text(runs),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&records(runs))?),
+        Format::Csv => {
+            println!("{}", HEADER.join(","));
+            for record in records(runs) {
+                println!("{}", cells(&record).map(|cell| csv_escape(&cell)).join(","));
+            }
+        }
+        Format::Markdown => {
+            println!("| {} |", HEADER.join(" | "));
+            println!("|{}", "---|".repeat(HEADER.len()));
+            for record in records(runs) {
+                println!("| {} |", cells(&record).map(|cell| markdown_escape(&cell)).join(" | "));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(csv_escape("CMZ"), "CMZ");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(markdown_escape("a|b\nc"), "a\\|b<br>c");
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A parse failure pointing at the part of the puzzle input that caused it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in characters
+    pub column: usize,
+    /// The offending text, empty when something was missing
+    pub snippet: String,
+    pub message: String,
+    /// The whole line, shown as the excerpt
+    text: String,
+}
+
+impl ParseError {
+    /// An error at `fragment`, which has to be a slice of `input`. Slicing the end of a line points just past its
+    /// last character, for input that is missing.
+    pub fn new(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        debug_assert!(
+            (start..=start + input.len()).contains(&(fragment.as_ptr() as usize)),
+            "fragment is not part of the input"
+        );
+        let offset = (fragment.as_ptr() as usize).saturating_sub(start).min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |newline| offset + newline);
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: fragment.to_string(),
+            message: message.into(),
+            text: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let width = self.snippet.lines().next().unwrap_or_default().chars().count().max(1);
+        writeln!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(self.column - 1), "^".repeat(width))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Attaches the position of `fragment` to an error that doesn't know where it happened.
+pub trait Locate<T> {
+    fn at(self, input: &str, fragment: &str) -> Result<T, ParseError>;
+}
+
+impl<T, E: fmt::Display> Locate<T> for Result<T, E> {
+    fn at(self, input: &str, fragment: &str) -> Result<T, ParseError> {
+        self.map_err(|e| ParseError::new(input, fragment, e.to_string()))
+    }
+}
+
+/// Parses `fragment` of `input`, e.g. a number, reporting where it went wrong.
+pub fn field<T>(input: &str, fragment: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fragment.parse().map_err(|e| ParseError::new(input, fragment, format!("{} {:?}", e, fragment)))
+}
+
+/// The position just past the end of `fragment`, for errors about something missing after it.
+pub fn after(fragment: &str) -> &str {
+    &fragment[fragment.len()..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let input = "1000\n2000\n30x0\n";
+        let line = input.lines().nth(2).unwrap();
+        let error = field::<u32>(input, line).unwrap_err();
+        assert_eq!((error.line, error.column, error.snippet.as_str()), (3, 1, "30x0"));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: invalid digit found in string \"30x0\"\n  |\n3 | 30x0\n  | ^^^^"
+        );
+
+        let error = ParseError::new(input, &line[2..3], "expected a digit");
+        assert_eq!(error.to_string(), "line 3, column 3: expected a digit\n  |\n3 | 30x0\n  |   ^");
+
+        let error = ParseError::new(input, after(input.lines().next().unwrap()), "missing value");
+        assert_eq!((error.line, error.column, error.snippet.as_str()), (1, 5, ""));
+        assert!(error.to_string().ends_with("1 | 1000\n  |     ^"));
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Maps `f` over `items` on up to `jobs` worker threads, returning results in the order of `items`.
+pub fn map<T, R, F>(items: Vec<T>, jobs: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let len = items.len();
+    let items = items.into_iter().map(|item| Mutex::new(Some(item))).collect::<Vec<_>>();
+    let results = (0..len).map(|_| Mutex::new(None)).collect::<Vec<_>>();
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, len.max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                if index >= len {
+                    break;
+                }
+                let item = items[index].lock().expect("item lock").take().expect("item taken once");
+                let result = f(item);
+                *results[index].lock().expect("result lock") = Some(result);
+            });
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.into_inner().expect("result lock").expect("every item processed"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_ordered() {
+        let items = (0..32).collect::<Vec<u64>>();
+        let results = map(items, 4, |item| {
+            thread::sleep(std::time::Duration::from_millis(32 - item));
+            item * 2
+        });
+        assert_eq!(results, (0..32).map(|item| item * 2).collect::<Vec<_>>());
+        assert!(map(Vec::<u64>::new(), 4, |item| item).is_empty());
+    }
+}
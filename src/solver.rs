@@ -0,0 +1,217 @@
+use crate::answer::{Answer, Diagnostic, Output};
+use color_eyre::Result;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Returned by parts that haven't been written yet, they are reported as pending instead of failing the day.
+#[derive(Debug)]
+pub struct NotImplemented;
+
+impl fmt::Display for NotImplemented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// A single puzzle, split into a parse phase and two parts that share the parsed input.
+pub trait Day {
+    type Input;
+    /// Labels shown in front of each answer in text output
+    const PART1_LABEL: Option<&'static str> = None;
+    const PART2_LABEL: Option<&'static str> = None;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(_input: &Self::Input) -> Result<Output> {
+        Err(NotImplemented.into())
+    }
+
+    fn part2(_input: &Self::Input) -> Result<Output> {
+        Err(NotImplemented.into())
+    }
+}
+
+/// How a single part of a day went.
+pub enum Status {
+    Solved(Output),
+    NotImplemented,
+    /// Not run because another part was selected
+    Skipped,
+}
+
+impl Status {
+    fn run<I>(part: u8, selected: Option<u8>, f: fn(&I) -> Result<Output>, input: &I) -> Result<(Status, Duration)> {
+        if selected.is_some_and(|selected| selected != part) {
+            return Ok((Status::Skipped, Duration::ZERO));
+        }
+        let now = Instant::now();
+        let status = match f(input) {
+            Ok(output) => Status::Solved(output),
+            Err(e) if e.downcast_ref::<NotImplemented>().is_some() => Status::NotImplemented,
+            Err(e) => return Err(e),
+        };
+        Ok((status, now.elapsed()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Solved(_) => "solved",
+            Status::NotImplemented => "not implemented",
+            Status::Skipped => "skipped",
+        }
+    }
+}
+
+pub struct Solution {
+    pub first: Status,
+    pub second: Status,
+    pub first_label: Option<&'static str>,
+    pub second_label: Option<&'static str>,
+}
+
+impl Solution {
+    pub fn status(&self, part: u8) -> &Status {
+        if part == 1 {
+            &self.first
+        } else {
+            &self.second
+        }
+    }
+
+    /// The answer for `part`, `None` when it wasn't solved.
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        match self.status(part) {
+            Status::Solved(output) => Some(&output.answer),
+            _ => None,
+        }
+    }
+
+    pub fn diagnostics(&self, part: u8) -> &[Diagnostic] {
+        match self.status(part) {
+            Status::Solved(output) => &output.diagnostics,
+            _ => &[],
+        }
+    }
+
+    /// Parts that have yet to be written.
+    pub fn pending(&self) -> usize {
+        [&self.first, &self.second]
+            .iter()
+            .filter(|status| matches!(status, Status::NotImplemented))
+            .count()
+    }
+
+    pub fn label(&self, part: u8) -> Option<&'static str> {
+        if part == 1 {
+            self.first_label
+        } else {
+            self.second_label
+        }
+    }
+
+    pub fn labelled(&self, part: u8) -> String {
+        match (self.answer(part), self.label(part)) {
+            (Some(answer), Some(label)) => format!("{} {}", label, answer.inline()),
+            (Some(answer), None) => answer.inline(),
+            (None, _) => self.status(part).name().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+    /// CPU time the solving thread used across all phases, where the platform reports it
+    pub cpu: Option<Duration>,
+}
+
+/// CPU time used so far by the calling thread.
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: `time` is a valid timespec for the call to write into
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    (result == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+impl Timings {
+    pub fn part(&self, part: u8) -> Duration {
+        if part == 1 {
+            self.part1
+        } else {
+            self.part2
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// Object safe view of a `Day` so days with different input types can be registered together.
+pub trait Solver: Sync {
+    /// Parses `input` and runs both parts, or only `part` when given.
+    fn solve(&self, input: &str, part: Option<u8>) -> Result<(Solution, Timings)>;
+}
+
+impl<D: Day + Sync> Solver for D {
+    fn solve(&self, input: &str, part: Option<u8>) -> Result<(Solution, Timings)> {
+        let cpu = thread_cpu_time();
+        let now = Instant::now();
+        let parsed = D::parse(input)?;
+        let parse = now.elapsed();
+
+        let (first, part1) = Status::run(1, part, D::part1, &parsed)?;
+        let (second, part2) = Status::run(2, part, D::part2, &parsed)?;
+
+        let solution = Solution {
+            first,
+            second,
+            first_label: D::PART1_LABEL,
+            second_label: D::PART2_LABEL,
+        };
+        let cpu = cpu.zip(thread_cpu_time()).map(|(start, end)| end.saturating_sub(start));
+        Ok((solution, Timings { parse, part1, part2, cpu }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Partial;
+
+    impl Day for Partial {
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.len())
+        }
+
+        fn part1(len: &Self::Input) -> Result<Output> {
+            Ok((*len).into())
+        }
+    }
+
+    #[test]
+    fn test_partial_day() {
+        let (solution, _) = Partial.solve("abc", None).unwrap();
+        assert_eq!(solution.answer(1), Some(&Answer::Integer(3)));
+        assert!(matches!(solution.status(2), Status::NotImplemented));
+        assert_eq!(solution.pending(), 1);
+        assert_eq!(solution.labelled(2), "not implemented");
+
+        let (solution, timings) = Partial.solve("abc", Some(2)).unwrap();
+        assert!(matches!(solution.status(1), Status::Skipped));
+        assert_eq!(timings.part1, Duration::ZERO);
+        assert_eq!(timings.cpu.is_some(), cfg!(unix));
+    }
+}
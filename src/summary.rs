@@ -0,0 +1,95 @@
+use crate::solver::{Solution, Timings};
+use color_eyre::Result;
+use std::time::Duration;
+
+pub struct DayRun {
+    pub year: u16,
+    pub day: usize,
+    pub result: Result<(Solution, Timings)>,
+}
+
+impl DayRun {
+    pub fn failed(&self) -> bool {
+        self.result.is_err()
+    }
+}
+
+fn row(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// A failed day as a table cell. Parse errors carry a multi-line excerpt that would break the table, so only the
+/// first line is shown, which still has the position and message.
+pub fn failure(e: &color_eyre::Report) -> String {
+    format!("FAILED: {}", format!("{:#}", e).lines().next().unwrap_or_default())
+}
+
+/// Prints `rows` under `header` with every column padded to its widest cell.
+pub fn table(header: &[String], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|cell| cell.chars().count()).collect::<Vec<_>>();
+    for cells in rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!("{}", row(header, &widths));
+    println!("{}", widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>().join("-+-"));
+    for cells in rows {
+        println!("{}", row(cells, &widths));
+    }
+}
+
+pub fn print(runs: &[DayRun]) {
+    let header = ["Day", "Part 1", "Part 2", "Parse", "Part 1 Time", "Part 2 Time", "Total"].map(String::from);
+    let rows = runs
+        .iter()
+        .map(|run| match &run.result {
+            Ok((solution, timings)) => vec![
+                run.day.to_string(),
+                solution.labelled(1),
+                solution.labelled(2),
+                format!("{:.2?}", timings.parse),
+                format!("{:.2?}", timings.part1),
+                format!("{:.2?}", timings.part2),
+                format!("{:.2?}", timings.total()),
+            ],
+            Err(e) => {
+                let mut cells = vec![run.day.to_string(), failure(e), String::new()];
+                cells.resize(header.len(), "-".to_string());
+                cells
+            }
+        })
+        .collect::<Vec<_>>();
+
+    table(&header, &rows);
+
+    let total = runs
+        .iter()
+        .filter_map(|run| run.result.as_ref().ok())
+        .map(|(_, timings)| timings.total())
+        .sum::<Duration>();
+    let failed = runs.iter().filter(|run| run.failed()).count();
+    let pending = runs
+        .iter()
+        .filter_map(|run| run.result.as_ref().ok())
+        .map(|(solution, _)| solution.pending())
+        .sum::<usize>();
+    println!();
+    if pending > 0 {
+        println!(
+            "Total runtime {:.2?} ({} days, {} failed, {} parts pending)",
+            total,
+            runs.len(),
+            failed,
+            pending
+        );
+    } else {
+        println!("Total runtime {:.2?} ({} days, {} failed)", total, runs.len(), failed);
+    }
+}
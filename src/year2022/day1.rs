@@ -1,9 +1,10 @@
-use super::Solution;
+use super::{Day, Output};
+use crate::parse::field;
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 
 #[derive(Default, Debug)]
-struct Elf {
+pub struct Elf {
     calories: Vec<isize>,
     sum: isize,
 }
@@ -16,7 +17,7 @@ fn parse(input: &str) -> Result<Vec<Elf>> {
             elves.push(current_elf);
             current_elf = Elf::default();
         } else {
-            let calories: isize = line.parse()?;
+            let calories: isize = field(input, line)?;
             current_elf.calories.push(calories);
             current_elf.sum += calories;
         }
@@ -33,14 +34,26 @@ fn part2(elves: &[Elf]) -> Result<isize> {
     Ok(elves[elves.len() - 3..].iter().fold(0, |sum, elf| sum + elf.sum))
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let mut elves = parse(input)?;
-    elves.sort_by(|a, b| a.sum.cmp(&b.sum));
+pub struct Day1;
 
-    Ok(Solution {
-        first: format!("Calories {}", part1(&elves)?),
-        second: format!("Three Total Calories {}", part2(&elves)?),
-    })
+impl Day for Day1 {
+    type Input = Vec<Elf>;
+    const PART1_LABEL: Option<&'static str> = Some("Calories");
+    const PART2_LABEL: Option<&'static str> = Some("Three Total Calories");
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut elves = parse(input)?;
+        elves.sort_by_key(|elf| elf.sum);
+        Ok(elves)
+    }
+
+    fn part1(elves: &Self::Input) -> Result<Output> {
+        Ok(Output::from(part1(elves)?).with("elves", elves.len()))
+    }
+
+    fn part2(elves: &Self::Input) -> Result<Output> {
+        Ok(part2(elves)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -65,7 +78,7 @@ mod tests {
 10000"#;
 
         let mut elves = parse(input).unwrap();
-        elves.sort_by(|a, b| a.sum.cmp(&b.sum));
+        elves.sort_by_key(|elf| elf.sum);
         assert_eq!(part1(&elves).unwrap(), 24000);
         assert_eq!(part2(&elves).unwrap(), 45000);
     }
@@ -1,5 +1,6 @@
-use super::Solution;
-use color_eyre::eyre::{bail, eyre};
+use super::{Day, Output};
+use crate::parse::{after, Locate, ParseError};
+use color_eyre::eyre::bail;
 use color_eyre::{Report, Result};
 
 #[derive(Debug, Copy, Clone)]
@@ -24,7 +25,7 @@ impl TryFrom<&str> for Action {
             "X" => Action::Lose,
             "Y" => Action::Draw,
             "Z" => Action::Win,
-            _ => bail!("Unexpected character"),
+            _ => bail!("Unexpected action {:?}", value),
         })
     }
 }
@@ -76,13 +77,13 @@ impl TryFrom<&str> for Move {
             "A" => Move::Rock,
             "B" => Move::Paper,
             "C" => Move::Scissors,
-            _ => bail!("Unknown character"),
+            _ => bail!("Unknown move {:?}", value),
         })
     }
 }
 
 #[derive(Debug)]
-struct Turn {
+pub struct Turn {
     opponent: Move,
     me: Move,
     action: Action,
@@ -104,23 +105,36 @@ fn parse(input: &str) -> Result<Vec<Turn>> {
         .lines()
         .map(|line| {
             let mut turn = line.split(' ');
-            let opponent = turn.next().ok_or_else(|| eyre!("Expected character"))?;
-            let me = turn.next().ok_or_else(|| eyre!("Expected character"))?;
+            let opponent = turn.next().filter(|opponent| !opponent.is_empty()).ok_or_else(|| ParseError::new(input, line, "Expected character"))?;
+            let me = turn.next().ok_or_else(|| ParseError::new(input, after(line), "Expected character"))?;
+            if let Some(extra) = turn.next() {
+                return Err(ParseError::new(input, extra, "Unexpected additional input").into());
+            }
             Ok(Turn {
-                opponent: Move::try_from(opponent)?,
-                me: Move::try_from(me)?,
-                action: Action::try_from(me)?,
+                opponent: Move::try_from(opponent).at(input, opponent)?,
+                me: Move::try_from(me).at(input, me)?,
+                action: Action::try_from(me).at(input, me)?,
             })
         })
         .collect::<Result<Vec<_>>>()
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let turns = parse(input)?;
-    Ok(Solution {
-        first: format!("{}", part1(&turns)),
-        second: format!("{}", part2(&turns)),
-    })
+pub struct Day2;
+
+impl Day for Day2 {
+    type Input = Vec<Turn>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(turns: &Self::Input) -> Result<Output> {
+        Ok(part1(turns).into())
+    }
+
+    fn part2(turns: &Self::Input) -> Result<Output> {
+        Ok(part2(turns).into())
+    }
 }
 
 #[cfg(test)]
@@ -135,5 +149,8 @@ C Z"#;
         let turns = parse(input).unwrap();
         assert_eq!(part1(&turns), 15);
         assert_eq!(part2(&turns), 12);
+
+        let error = parse("A Y\nB Q").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 3: Unknown move \"Q\"\n  |\n2 | B Q\n  |   ^");
     }
 }
@@ -1,4 +1,5 @@
-use super::Solution;
+use super::{Day, Output};
+use crate::parse::ParseError;
 use ahash::HashSet;
 use color_eyre::eyre::bail;
 use color_eyre::Result;
@@ -7,7 +8,7 @@ use color_eyre::Result;
 struct Compartment(HashSet<char>);
 
 #[derive(Debug)]
-struct Rucksack {
+pub struct Rucksack {
     compartment1: Compartment,
     compartment2: Compartment,
     all: HashSet<char>,
@@ -36,7 +37,7 @@ fn part1(rucksacks: &[Rucksack]) -> Result<i32> {
 }
 
 fn part2(rucksacks: &[Rucksack]) -> Result<i32> {
-    if rucksacks.len() % 3 != 0 {
+    if !rucksacks.len().is_multiple_of(3) {
         bail!("Expected groups of 3");
     }
     rucksacks.chunks(3).try_fold(0, |acc, chunk| {
@@ -49,44 +50,44 @@ fn part2(rucksacks: &[Rucksack]) -> Result<i32> {
     })
 }
 
-fn parse_compartment(input: &str) -> Result<Compartment> {
-    Ok(Compartment(
-        input
-            .chars()
-            .map(|item| {
-                if !item.is_ascii_alphabetic() {
-                    bail!("Unexpected character")
-                }
-                Ok(item)
-            })
-            .collect::<Result<HashSet<char>>>()?,
-    ))
-}
-
 fn parse(input: &str) -> Result<Vec<Rucksack>> {
     input
         .lines()
         .map(|line| {
+            // Checked up front so that splitting the line in half can't land inside a character
+            if let Some((index, item)) = line.char_indices().find(|(_, item)| !item.is_ascii_alphabetic()) {
+                return Err(ParseError::new(input, &line[index..index + item.len_utf8()], "Unexpected character").into());
+            }
             if line.len() % 2 != 0 {
-                bail!("Uneven compartment");
+                return Err(ParseError::new(input, line, "Uneven compartment").into());
             }
             let all = line.chars().collect();
             let (compartment1, compartment2) = line.split_at(line.len() / 2);
             Ok(Rucksack {
-                compartment1: parse_compartment(compartment1)?,
-                compartment2: parse_compartment(compartment2)?,
+                compartment1: Compartment(compartment1.chars().collect()),
+                compartment2: Compartment(compartment2.chars().collect()),
                 all,
             })
         })
         .collect()
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let sacks = parse(input)?;
-    Ok(Solution {
-        first: format!("{}", part1(&sacks)?),
-        second: format!("{}", part2(&sacks)?),
-    })
+pub struct Day3;
+
+impl Day for Day3 {
+    type Input = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(sacks: &Self::Input) -> Result<Output> {
+        Ok(part1(sacks)?.into())
+    }
+
+    fn part2(sacks: &Self::Input) -> Result<Output> {
+        Ok(part2(sacks)?.into())
+    }
 }
 
 #[cfg(test)]
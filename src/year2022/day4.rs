@@ -1,23 +1,23 @@
 use super::*;
-use color_eyre::eyre::eyre;
+use crate::parse::{after, field, ParseError};
 use color_eyre::Result;
 use std::ops::RangeInclusive;
 
 #[derive(Debug)]
-struct Assignment {
+pub struct Assignment {
     first: RangeInclusive<i32>,
     second: RangeInclusive<i32>,
 }
 
-fn parse_range(input: &str) -> Result<RangeInclusive<i32>> {
-    let mut range = input.split('-');
-    let start = range.next().ok_or_else(|| eyre!("Missing start of range"))?;
-    let end = range.next().ok_or_else(|| eyre!("Missing end of range"))?;
-    if range.next().is_some() {
-        bail!("Unexpected additional input");
+fn parse_range(input: &str, range: &str) -> Result<RangeInclusive<i32>, ParseError> {
+    let mut bounds = range.split('-');
+    let start = bounds.next().unwrap_or(range);
+    let end = bounds.next().ok_or_else(|| ParseError::new(input, after(range), "Missing end of range"))?;
+    if let Some(extra) = bounds.next() {
+        return Err(ParseError::new(input, extra, "Unexpected additional input"));
     }
 
-    Ok(RangeInclusive::new(start.parse()?, end.parse()?))
+    Ok(RangeInclusive::new(field(input, start)?, field(input, end)?))
 }
 
 fn parse(input: &str) -> Result<Vec<Assignment>> {
@@ -25,18 +25,19 @@ fn parse(input: &str) -> Result<Vec<Assignment>> {
         .lines()
         .map(|line| {
             let mut pair = line.split(',');
-            let first = pair.next().ok_or_else(|| eyre!("Unexpected end of input"))?;
-            let second = pair.next().ok_or_else(|| eyre!("Unexpected end of input"))?;
-            if pair.next().is_some() {
-                bail!("Unexpected additional input");
+            let first = pair.next().unwrap_or(line);
+            let second = pair.next().ok_or_else(|| ParseError::new(input, after(line), "Unexpected end of input"))?;
+            if let Some(extra) = pair.next() {
+                return Err(ParseError::new(input, extra, "Unexpected additional input").into());
             }
             Ok(Assignment {
-                first: parse_range(first)?,
-                second: parse_range(second)?,
+                first: parse_range(input, first)?,
+                second: parse_range(input, second)?,
             })
         })
         .collect()
 }
+
 fn part2(assignments: &[Assignment]) -> i32 {
     assignments.iter().fold(0, |acc, assignment| {
         let first = &assignment.first;
@@ -61,12 +62,22 @@ fn part1(assignments: &[Assignment]) -> i32 {
     })
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let assignments = parse(input)?;
-    Ok(Solution {
-        first: format!("{}", part1(&assignments)),
-        second: format!("{}", part2(&assignments)),
-    })
+pub struct Day4;
+
+impl Day for Day4 {
+    type Input = Vec<Assignment>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(assignments: &Self::Input) -> Result<Output> {
+        Ok(part1(assignments).into())
+    }
+
+    fn part2(assignments: &Self::Input) -> Result<Output> {
+        Ok(part2(assignments).into())
+    }
 }
 
 #[cfg(test)]
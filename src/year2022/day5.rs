@@ -1,4 +1,5 @@
 use super::*;
+use crate::parse::{field, ParseError};
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use regex::Regex;
@@ -18,7 +19,7 @@ struct Instruction {
 }
 
 #[derive(Debug, Clone)]
-struct Cargo {
+pub struct Cargo {
     stacks: Vec<VecDeque<char>>,
     instructions: Vec<Instruction>,
 }
@@ -32,27 +33,38 @@ fn parse(input: &str) -> Result<Cargo> {
     for line in input.lines() {
         match state {
             State::Stack => {
-                let row = line.chars().collect::<Vec<_>>();
-                row.chunks(4).enumerate().for_each(|(column, value)| {
+                let row = line.char_indices().collect::<Vec<_>>();
+                for (column, value) in row.chunks(4).enumerate() {
                     if stacks.len() < column + 1 {
                         stacks.push(VecDeque::new())
                     }
-                    if value[1].is_alphabetic() {
-                        stacks[column].push_front(value[1]);
-                    } else if value[1].is_numeric() {
+                    let &(_, label) = value.get(1).ok_or_else(|| ParseError::new(input, &line[value[0].0..], "Expected a crate or stack number"))?;
+                    if label.is_alphabetic() {
+                        stacks[column].push_front(label);
+                    } else if label.is_numeric() {
                         state = State::Instructions;
                     }
-                });
+                }
             }
             State::Instructions => {
                 if line.is_empty() {
                     continue;
                 }
-                let caps = instruction_regex.captures(line).ok_or_else(|| eyre!("Failed to find any captures"))?;
+                let caps = instruction_regex
+                    .captures(line)
+                    .ok_or_else(|| ParseError::new(input, line, "Expected \"move <amount> from <stack> to <stack>\""))?;
+                let stack = |index: usize| -> Result<i32> {
+                    let capture = caps.get(index).ok_or_else(|| eyre!("Missing capture {}", index))?.as_str();
+                    let stack = field(input, capture)?;
+                    if !(1..=stacks.len() as i32).contains(&stack) {
+                        return Err(ParseError::new(input, capture, format!("No stack {}, there are {}", stack, stacks.len())).into());
+                    }
+                    Ok(stack)
+                };
                 instructions.push(Instruction {
-                    amount: caps.get(1).ok_or_else(|| eyre!("Missing amount capture"))?.as_str().parse()?,
-                    start: caps.get(2).ok_or_else(|| eyre!("Missing amount capture"))?.as_str().parse()?,
-                    end: caps.get(3).ok_or_else(|| eyre!("Missing amount capture"))?.as_str().parse()?,
+                    amount: field(input, caps.get(1).ok_or_else(|| eyre!("Missing amount capture"))?.as_str())?,
+                    start: stack(2)?,
+                    end: stack(3)?,
                 });
             }
         }
@@ -100,12 +112,22 @@ fn part2(mut cargo: Cargo) -> Result<String> {
     Ok(print_stacks(&cargo))
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let cargo = parse(input)?;
-    Ok(Solution {
-        first: part1(cargo.clone())?,
-        second: part2(cargo)?,
-    })
+pub struct Day5;
+
+impl Day for Day5 {
+    type Input = Cargo;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(cargo: &Self::Input) -> Result<Output> {
+        part1(cargo.clone()).map(Output::from)
+    }
+
+    fn part2(cargo: &Self::Input) -> Result<Output> {
+        part2(cargo.clone()).map(Output::from)
+    }
 }
 
 #[cfg(test)]
@@ -24,11 +24,22 @@ fn part2(input: &str) -> Option<u32> {
     find_sequence(input, 14)
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    Ok(Solution {
-        first: format!("{}", part1(input).ok_or_else(|| eyre!("Failed to find start"))?),
-        second: format!("{}", part2(input).ok_or_else(|| eyre!("Failed to find start"))?),
-    })
+pub struct Day6;
+
+impl Day for Day6 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Output> {
+        Ok(part1(input).ok_or_else(|| eyre!("Failed to find start"))?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Output> {
+        Ok(part2(input).ok_or_else(|| eyre!("Failed to find start"))?.into())
+    }
 }
 
 #[cfg(test)]
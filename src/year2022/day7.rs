@@ -1,11 +1,12 @@
 use super::*;
+use crate::parse::{after, field, ParseError};
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use ego_tree::{NodeRef, Tree};
 use std::path::PathBuf;
 
 #[derive(Debug)]
-struct PathEntry {
+pub struct PathEntry {
     size: i32,
     path: PathBuf,
 }
@@ -22,11 +23,18 @@ fn parse(input: &str) -> Result<Tree<PathEntry>> {
     let mut current_node_id = rtn.root().id();
     for line in input.lines() {
         let inputs = line.split(' ').collect::<Vec<_>>();
+        let token = |index: usize, expected: &str| {
+            inputs
+                .get(index)
+                .copied()
+                .ok_or_else(|| ParseError::new(input, after(line), format!("Expected {}", expected)))
+        };
         match inputs[0] {
             "$" => {
                 let mut current_node = rtn.get(current_node_id).ok_or_else(|| eyre!("Could not find node id"))?;
-                match inputs[1] {
-                    "cd" => match inputs[2] {
+                let command = token(1, "a command")?;
+                match command {
+                    "cd" => match token(2, "a directory")? {
                         ".." => {
                             cwd.pop();
                             if let Some(parent) = current_node.parent() {
@@ -44,17 +52,17 @@ fn parse(input: &str) -> Result<Tree<PathEntry>> {
                             current_node = current_node
                                 .children()
                                 .find(|entry| entry.value().path == cwd)
-                                .ok_or_else(|| eyre!("Could not find path at {:?}", cwd))?;
+                                .ok_or_else(|| ParseError::new(input, dir, format!("Could not find path at {:?}", cwd)))?;
                         }
                     },
                     "ls" => {}
-                    _ => bail!("Unexpected command"),
+                    _ => return Err(ParseError::new(input, command, "Unexpected command").into()),
                 }
                 current_node_id = current_node.id();
             }
             _ => {
                 let mut path = cwd.clone();
-                path.push(inputs[1]);
+                path.push(token(1, "a name")?);
                 match inputs[0] {
                     "dir" => {
                         // create new node
@@ -64,7 +72,7 @@ fn parse(input: &str) -> Result<Tree<PathEntry>> {
                     _ => {
                         // create new file
                         let mut tree_node = rtn.get_mut(current_node_id).ok_or_else(|| eyre!("Faild to find node"))?;
-                        let size: i32 = inputs[0].parse()?;
+                        let size: i32 = field(input, inputs[0])?;
                         tree_node.append(PathEntry::new(size, path));
                     }
                 }
@@ -104,13 +112,22 @@ fn part2(tree: &Tree<PathEntry>) -> Result<i32> {
         .size)
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let tree = parse(input)?;
+pub struct Day7;
 
-    Ok(Solution {
-        first: format!("{}", part1(&tree)),
-        second: format!("{}", part2(&tree)?),
-    })
+impl Day for Day7 {
+    type Input = Tree<PathEntry>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1(tree: &Self::Input) -> Result<Output> {
+        Ok(part1(tree).into())
+    }
+
+    fn part2(tree: &Self::Input) -> Result<Output> {
+        Ok(part2(tree)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -147,4 +164,15 @@ $ ls
         assert_eq!(part1(&paths), 95437);
         assert_eq!(part2(&paths).unwrap(), 24933642);
     }
+
+    #[test]
+    fn test_malformed() {
+        let error = parse("$ cd /\n$ cd\n").unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.message, "Expected a directory");
+
+        let error = parse("$ cd /\n$ ls\n12x4 a.txt").unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseError>().unwrap().snippet, "12x4");
+    }
 }
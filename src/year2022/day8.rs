@@ -1,17 +1,18 @@
 use super::*;
+use crate::parse::ParseError;
 use color_eyre::eyre::eyre;
 use color_eyre::Result;
 use std::fmt;
 
 #[derive(Debug, Clone)]
-struct Tree {
+pub struct Tree {
     height: u8,
     visible: bool,
     score: i32,
 }
 
 #[derive(Clone)]
-struct Forest {
+pub struct Forest {
     trees: Vec<Tree>,
     width: usize,
     height: usize,
@@ -169,10 +170,13 @@ fn parse(input: &str) -> Result<Forest> {
     let mut width = 0;
     for line in input.lines() {
         let mut curr_width = 0;
-        for char in line.chars() {
+        for (index, char) in line.char_indices() {
+            let height = char
+                .to_digit(10)
+                .ok_or_else(|| ParseError::new(input, &line[index..index + char.len_utf8()], "Failed to convert to digit"))?;
             trees.push(Tree {
                 visible: false,
-                height: char.to_digit(10).ok_or_else(|| eyre!("Failed to convert to digit"))? as u8,
+                height: height as u8,
                 score: 0,
             });
             curr_width += 1;
@@ -180,20 +184,32 @@ fn parse(input: &str) -> Result<Forest> {
         if width == 0 {
             width = curr_width;
         } else if width != curr_width {
-            bail!("Unexpected line width")
+            return Err(ParseError::new(input, line, format!("Unexpected line width {}, expected {}", curr_width, width)).into());
         }
     }
+    if width == 0 {
+        return Err(ParseError::new(input, input, "Expected at least one row of trees").into());
+    }
 
     Ok(Forest::new(trees.len() / width, width, trees))
 }
 
-pub fn solve(input: &str) -> Result<Solution> {
-    let mut forest = parse(input)?;
+pub struct Day8;
+
+impl Day for Day8 {
+    type Input = Forest;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
 
-    Ok(Solution {
-        first: format!("{}", part1(&mut forest)),
-        second: format!("{}", part2(&mut forest)?),
-    })
+    fn part1(forest: &Self::Input) -> Result<Output> {
+        Ok(part1(&mut forest.clone()).into())
+    }
+
+    fn part2(forest: &Self::Input) -> Result<Output> {
+        Ok(part2(&mut forest.clone())?.into())
+    }
 }
 
 #[cfg(test)]
@@ -211,14 +227,14 @@ mod tests {
         let mut forest = parse(input).unwrap();
         assert_eq!(part1(&mut forest), 21);
 
-        assert_eq!(score_left(&mut forest, 2, 1), 1);
-        assert_eq!(score_right(&mut forest, 2, 1), 2);
-        assert_eq!(score_up(&mut forest, 2, 1), 1);
-        assert_eq!(score_down(&mut forest, 2, 1), 2);
-        assert_eq!(score_left(&mut forest, 2, 3), 2);
-        assert_eq!(score_right(&mut forest, 2, 3), 2);
-        assert_eq!(score_up(&mut forest, 2, 3), 2);
-        assert_eq!(score_down(&mut forest, 2, 3), 1);
+        assert_eq!(score_left(&forest, 2, 1), 1);
+        assert_eq!(score_right(&forest, 2, 1), 2);
+        assert_eq!(score_up(&forest, 2, 1), 1);
+        assert_eq!(score_down(&forest, 2, 1), 2);
+        assert_eq!(score_left(&forest, 2, 3), 2);
+        assert_eq!(score_right(&forest, 2, 3), 2);
+        assert_eq!(score_up(&forest, 2, 3), 2);
+        assert_eq!(score_down(&forest, 2, 3), 1);
 
         assert_eq!(part2(&mut forest).unwrap(), 8);
     }
@@ -0,0 +1,3 @@
+use super::*;
+
+days! {day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8}